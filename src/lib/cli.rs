@@ -0,0 +1,117 @@
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail};
+use clap::{Args, Parser, Subcommand};
+
+use crate::config::{Config, CONFIG_PATH_ENV};
+use crate::infrastructure::app_runner::AppRunner;
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::services::api_key_service_impl::ApiKeyServiceImpl;
+
+#[derive(Parser)]
+#[command(name = "content-search-service", version, about = "Full text search over catalogue content")]
+pub struct Cli {
+    /// TOML configuration file, layered under APP__* environment variables
+    #[arg(long, short, global = true, env = CONFIG_PATH_ENV)]
+    config: Option<String>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Start the http server and the scheduled indexer (default)
+    Serve(ServeArgs),
+    /// Rebuild every index once and exit
+    Reindex(ReindexArgs),
+    /// Build the index of a single type and print the ranked matches of a search
+    Query(QueryArgs),
+    /// Validate the configuration and print its effective values with secrets redacted
+    CheckConfig,
+    /// Print the SHA-256 of an API key read from stdin, as expected by `auth.keys.key_sha256`
+    HashApiKey,
+}
+
+#[derive(Args, Default)]
+pub struct ServeArgs {
+    /// Overrides `server.host`
+    #[arg(long)]
+    host: Option<String>,
+    /// Overrides `server.port`
+    #[arg(long, short)]
+    port: Option<u16>,
+}
+
+#[derive(Args)]
+pub struct ReindexArgs {
+    /// Directory where every index is exported as a snapshot archive
+    #[arg(long)]
+    export: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct QueryArgs {
+    /// Content type, e.g. MOVIE, TV, GAME or RECIPE
+    #[arg(long = "type", short = 't')]
+    entity: String,
+    /// Index language, e.g. EN or ES
+    #[arg(long, short, default_value = "EN")]
+    lang: String,
+    /// Snapshot directory to load the index from instead of the database
+    #[arg(long)]
+    snapshot: Option<PathBuf>,
+    /// Search keywords
+    #[arg(required = true)]
+    keywords: Vec<String>,
+}
+
+impl Cli {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let mut config = Config::load_from(self.config.as_deref())?;
+
+        match self.command.unwrap_or(Command::Serve(ServeArgs::default())) {
+            Command::Serve(args) => {
+                if let Some(host) = args.host {
+                    config.server_mut().set_host(host);
+                }
+                if let Some(port) = args.port {
+                    config.server_mut().set_port(port);
+                }
+                AppRunner::run(config, None).await
+            }
+            Command::Reindex(args) => {
+                AppRunner::reindex(config, args.export.as_deref()).await
+            }
+            Command::Query(args) => {
+                let entity = Entity::try_from(args.entity.to_ascii_uppercase().as_str())
+                    .map_err(|_| anyhow!("unknown content type '{}'", args.entity))?;
+                let language = Language::try_from(args.lang.to_ascii_uppercase().as_str())
+                    .map_err(|_| anyhow!("unsupported language '{}'", args.lang))?;
+
+                let results = AppRunner::query(config, entity, language, &args.keywords.join(" "), args.snapshot.as_deref()).await?;
+                println!("{:>4}  {:>10}  {:>8}  title", "rank", "id", "score");
+                for (rank, doc) in results.iter().enumerate() {
+                    println!("{:>4}  {:>10}  {:>8.4}  {}", rank + 1, doc.id(), doc.score(), doc.title());
+                }
+                Ok(())
+            }
+            Command::CheckConfig => {
+                print!("{}", config.to_redacted_toml()?);
+                Ok(())
+            }
+            Command::HashApiKey => {
+                // Read from stdin rather than argv, so the plaintext key stays out of shell history and `ps`
+                let mut key = String::new();
+                std::io::stdin().lock().read_line(&mut key)?;
+                let key = key.trim_end_matches(['\r', '\n']);
+                if key.is_empty() {
+                    bail!("expected the API key on stdin");
+                }
+                println!("{}", ApiKeyServiceImpl::hash_key(key));
+                Ok(())
+            }
+        }
+    }
+}
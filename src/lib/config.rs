@@ -1,23 +1,55 @@
+use std::collections::HashMap;
 use std::env;
-use std::sync::LazyLock;
 
+use percent_encoding::percent_decode_str;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
 
+use crate::config::analytics_config::AnalyticsConfig;
+use crate::config::auth_config::AuthConfig;
+use crate::config::blocklist_config::BlocklistConfig;
+use crate::config::change_feed_config::ChangeFeedConfig;
+use crate::config::curation_config::CurationConfig;
 use crate::config::database_config::DatabaseConfig;
+use crate::config::feedback_config::FeedbackConfig;
 use crate::config::indexer_runner_config::IndexerRunnerConfig;
 use crate::config::logger_config::LoggerConfig;
+use crate::config::search_cache_config::SearchCacheConfig;
 use crate::config::server_config::ServerConfig;
+use crate::config::snapshot_config::SnapshotConfig;
+use crate::config::source_config::SourcesConfig;
 
 pub mod server_config;
 pub mod database_config;
-mod indexer_runner_config;
-mod logger_config;
+pub mod indexer_runner_config;
+pub mod logger_config;
+pub mod snapshot_config;
+pub mod source_config;
+pub mod search_cache_config;
+pub mod auth_config;
+pub mod analytics_config;
+pub mod feedback_config;
+pub mod curation_config;
+pub mod blocklist_config;
+pub mod change_feed_config;
+pub mod index_writer_config;
 
 pub const CONFIG_PATH_ENV: &str = "CONFIG_PATH";
+pub const DATABASE_URL_ENV: &str = "DATABASE_URL";
+pub const ENV_PREFIX: &str = "APP";
+pub const ENV_SEPARATOR: &str = "__";
+pub const REDACTED: &str = "********";
 
-pub static CONFIG: LazyLock<Config> = LazyLock::new(|| {
-    Config::get().expect("Failed loading configuration")
-});
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed loading configuration: {0}")]
+    Load(#[from] config::ConfigError),
+    #[error("invalid {DATABASE_URL_ENV}: {0}")]
+    DatabaseUrl(String),
+    #[error("invalid configuration:\n - {}", .0.join("\n - "))]
+    Invalid(Vec<String>),
+}
 
 #[derive(Deserialize, Serialize)]
 pub struct Config {
@@ -25,16 +57,141 @@ pub struct Config {
     server: ServerConfig,
     indexer_runner: IndexerRunnerConfig,
     logger: LoggerConfig,
+    #[serde(default)]
+    snapshot: SnapshotConfig,
+    #[serde(default)]
+    sources: SourcesConfig,
+    #[serde(default)]
+    search_cache: SearchCacheConfig,
+    #[serde(default)]
+    auth: AuthConfig,
+    #[serde(default)]
+    analytics: AnalyticsConfig,
+    #[serde(default)]
+    feedback: FeedbackConfig,
+    #[serde(default)]
+    curation: CurationConfig,
+    #[serde(default)]
+    blocklist: BlocklistConfig,
+    #[serde(default)]
+    change_feed: ChangeFeedConfig,
 }
 
 
 impl Config {
-    fn get() -> anyhow::Result<Self> {
-        let config_path = env::var(CONFIG_PATH_ENV)?;
-        let config = config::Config::builder()
-            .add_source(config::File::with_name(config_path.as_str()))
-            .build()?;
-        Ok(config.try_deserialize::<Self>()?)
+    /// Loads the configuration from the file at `CONFIG_PATH`, if set.
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::load_from(env::var(CONFIG_PATH_ENV).ok().as_deref())
+    }
+
+    /// Layers defaults, the optional TOML file, `APP__SECTION__KEY` environment variables and
+    /// `DATABASE_URL`, in increasing precedence, then validates the result.
+    pub fn load_from(config_path: Option<&str>) -> Result<Self, ConfigError> {
+        Self::load_with_env(config_path, env::vars().collect())
+    }
+
+    /// Same as [`Config::load_from`] reading the environment variables from `vars` instead of the
+    /// process environment, which tests cannot change safely while others run.
+    pub fn load_with_env(config_path: Option<&str>, vars: HashMap<String, String>) -> Result<Self, ConfigError> {
+        let database_url = vars.get(DATABASE_URL_ENV).cloned();
+        let mut builder = config::Config::builder()
+            .set_default("server.host", "127.0.0.1")?
+            .set_default("server.port", 8080)?
+            .set_default("database.db_name", "postgres")?
+            .set_default("database.username", "postgres")?
+            .set_default("database.password", "")?
+            .set_default("database.host", "localhost")?
+            .set_default("database.port", 5432)?
+            .set_default("database.max_connections", 20)?
+            .set_default("database.min_connections", 1)?
+            .set_default("indexer_runner.batch_size", 1000)?
+            .set_default("indexer_runner.interval", 86400)?
+            .set_default("indexer_runner.wait_until_index", false)?
+            .set_default("logger.enabled", true)?
+            .set_default("logger.level", "INFO")?;
+
+        if let Some(config_path) = config_path {
+            builder = builder.add_source(config::File::with_name(config_path));
+        }
+
+        builder = builder.add_source(config::Environment::with_prefix(ENV_PREFIX)
+            .prefix_separator(ENV_SEPARATOR)
+            .separator(ENV_SEPARATOR)
+            .try_parsing(true)
+            .source(Some(vars)));
+
+        if let Some(database_url) = database_url {
+            for (key, value) in Self::database_url_overrides(&database_url)? {
+                builder = builder.set_override(key, value)?;
+            }
+        }
+
+        let config = builder.build()?.try_deserialize::<Self>()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reports every invalid setting at once instead of failing on the first one.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+        self.database.validate(&mut errors);
+        self.server.validate(&mut errors);
+        self.indexer_runner.validate(&mut errors);
+        self.logger.validate(&mut errors);
+        self.snapshot.validate(&mut errors);
+        self.sources.validate(&mut errors);
+        self.search_cache.validate(&mut errors);
+        self.auth.validate(&mut errors);
+        self.analytics.validate(&mut errors);
+        self.feedback.validate(&mut errors);
+        self.curation.validate(&mut errors);
+        self.blocklist.validate(&mut errors);
+        self.change_feed.validate(&mut errors);
+        if self.change_feed.enabled() && self.snapshot.skip_database() {
+            errors.push("change_feed requires the database, it cannot be enabled with snapshot.skip_database".to_string());
+        }
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(ConfigError::Invalid(errors))
+    }
+
+    fn database_url_overrides(database_url: &str) -> Result<Vec<(&'static str, String)>, ConfigError> {
+        let url = Url::parse(database_url).map_err(|err| ConfigError::DatabaseUrl(err.to_string()))?;
+        if !matches!(url.scheme(), "postgres" | "postgresql") {
+            return Err(ConfigError::DatabaseUrl(format!("unsupported scheme '{}'", url.scheme())));
+        }
+
+        let decode = |value: &str| percent_decode_str(value).decode_utf8_lossy().to_string();
+
+        let mut overrides = Vec::new();
+        if let Some(host) = url.host_str() {
+            overrides.push(("database.host", host.to_string()));
+        }
+        if let Some(port) = url.port() {
+            overrides.push(("database.port", port.to_string()));
+        }
+        if !url.username().is_empty() {
+            overrides.push(("database.username", decode(url.username())));
+        }
+        if let Some(password) = url.password() {
+            overrides.push(("database.password", decode(password)));
+        }
+        let db_name = url.path().trim_start_matches('/');
+        if !db_name.is_empty() {
+            overrides.push(("database.db_name", decode(db_name)));
+        }
+        Ok(overrides)
+    }
+
+    /// Effective configuration as TOML with secrets replaced by [`REDACTED`].
+    pub fn to_redacted_toml(&self) -> anyhow::Result<String> {
+        let mut value = toml::Value::try_from(self)?;
+        if let Some(password) = value.get_mut("database").and_then(|database| database.get_mut("password")) {
+            *password = toml::Value::String(REDACTED.to_string());
+        }
+        Ok(toml::to_string_pretty(&value)?)
     }
 
     pub fn database(&self) -> &DatabaseConfig {
@@ -49,6 +206,10 @@ impl Config {
         &self.server
     }
 
+    pub fn server_mut(&mut self) -> &mut ServerConfig {
+        &mut self.server
+    }
+
     pub fn indexer_runner(&self) -> &IndexerRunnerConfig {
         &self.indexer_runner
     }
@@ -56,4 +217,45 @@ impl Config {
     pub fn logger(&self) -> &LoggerConfig {
         &self.logger
     }
+
+    pub fn snapshot(&self) -> &SnapshotConfig {
+        &self.snapshot
+    }
+
+    pub fn sources(&self) -> &SourcesConfig {
+        &self.sources
+    }
+
+    pub fn search_cache(&self) -> &SearchCacheConfig {
+        &self.search_cache
+    }
+
+    pub fn auth(&self) -> &AuthConfig {
+        &self.auth
+    }
+
+    pub fn analytics(&self) -> &AnalyticsConfig {
+        &self.analytics
+    }
+
+    pub fn feedback(&self) -> &FeedbackConfig {
+        &self.feedback
+    }
+
+    pub fn curation(&self) -> &CurationConfig {
+        &self.curation
+    }
+
+    pub fn blocklist(&self) -> &BlocklistConfig {
+        &self.blocklist
+    }
+
+    pub fn change_feed(&self) -> &ChangeFeedConfig {
+        &self.change_feed
+    }
+
+    /// Whether the database is needed at all, either skipped for snapshots or replaced by other data sources.
+    pub fn uses_database(&self) -> bool {
+        !self.snapshot.skip_database() && self.sources.uses_database()
+    }
 }
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Opt-in recording of every search, written asynchronously so requests never wait on the sink.
+#[derive(Deserialize, Serialize)]
+pub struct AnalyticsConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    sink: AnalyticsSinkConfig,
+    #[serde(default = "default_buffer_size")]
+    buffer_size: usize,
+    #[serde(default = "default_top_ids")]
+    top_ids: usize,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum AnalyticsSinkConfig {
+    /// `analytics.search_events` table, created on the first write.
+    #[default]
+    Database,
+    /// JSONL file rotated to `<path>.1` ... `<path>.<max_files>` once it reaches `max_bytes`.
+    File {
+        path: String,
+        #[serde(default = "default_max_bytes")]
+        max_bytes: u64,
+        #[serde(default = "default_max_files")]
+        max_files: usize,
+    },
+}
+
+fn default_buffer_size() -> usize {
+    10_000
+}
+
+fn default_top_ids() -> usize {
+    10
+}
+
+fn default_max_bytes() -> u64 {
+    100 * 1024 * 1024
+}
+
+fn default_max_files() -> usize {
+    5
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self { enabled: false, sink: AnalyticsSinkConfig::default(), buffer_size: default_buffer_size(), top_ids: default_top_ids() }
+    }
+}
+
+impl AnalyticsConfig {
+    pub fn new(enabled: bool, sink: AnalyticsSinkConfig, buffer_size: usize, top_ids: usize) -> Self {
+        Self { enabled, sink, buffer_size, top_ids }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn sink(&self) -> &AnalyticsSinkConfig {
+        &self.sink
+    }
+
+    /// Events waiting to be written, further events are dropped until the sink catches up.
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Leading result ids kept per event.
+    pub fn top_ids(&self) -> usize {
+        self.top_ids
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if !self.enabled {
+            return;
+        }
+        if self.buffer_size == 0 {
+            errors.push("analytics.buffer_size must be greater than 0".to_string());
+        }
+        if let AnalyticsSinkConfig::File { path, max_bytes, max_files } = &self.sink {
+            let parent = Path::new(path).parent().filter(|parent| !parent.as_os_str().is_empty());
+            if path.is_empty() || parent.is_some_and(|parent| !parent.is_dir()) {
+                errors.push(format!("analytics.sink.path '{path}' is not in an existing directory"));
+            }
+            if *max_bytes == 0 || *max_files == 0 {
+                errors.push("analytics.sink max_bytes and max_files must be greater than 0".to_string());
+            }
+        }
+    }
+}
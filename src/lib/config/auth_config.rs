@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// API keys allowed to call the service, `enabled = false` keeps the search routes open and refuses the write and admin ones.
+#[derive(Deserialize, Serialize, Default)]
+pub struct AuthConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    keys: Vec<ApiKeyConfig>,
+}
+
+/// Client identified by the SHA-256 of its key, so the configuration never holds usable secrets.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct ApiKeyConfig {
+    name: String,
+    key_sha256: String,
+    #[serde(default = "default_scopes")]
+    scopes: Vec<Scope>,
+    rate_limit: Option<RateLimitConfig>,
+    quota: Option<QuotaConfig>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Search routes.
+    Search,
+    /// Management routes under `/admin`, implies `search` and `write`.
+    Admin,
+    /// Document upserts and deletes under `/documents`.
+    Write,
+}
+
+/// Token bucket holding up to `burst` requests, refilled by `per_second` tokens every second.
+#[derive(Deserialize, Serialize, Clone, Copy)]
+pub struct RateLimitConfig {
+    per_second: f64,
+    burst: u32,
+}
+
+/// At most `requests` requests every `period` seconds, counted from the first request of the period.
+#[derive(Deserialize, Serialize, Clone, Copy)]
+pub struct QuotaConfig {
+    requests: u64,
+    period: u64,
+}
+
+fn default_scopes() -> Vec<Scope> {
+    vec![Scope::Search]
+}
+
+impl AuthConfig {
+    pub fn new(enabled: bool, keys: Vec<ApiKeyConfig>) -> Self {
+        Self { enabled, keys }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn keys(&self) -> &[ApiKeyConfig] {
+        &self.keys
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if self.enabled && self.keys.is_empty() {
+            errors.push("auth.keys must not be empty when auth is enabled".to_string());
+        }
+
+        let mut names = HashSet::new();
+        let mut hashes = HashSet::new();
+        for key in self.keys.iter() {
+            let name = &key.name;
+            if !names.insert(name.as_str()) {
+                errors.push(format!("auth.keys: duplicated name '{name}'"));
+            }
+            if key.key_sha256.len() != 64 || !key.key_sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                errors.push(format!("auth.keys.{name}.key_sha256 must be a hex encoded SHA-256 digest"));
+            } else if !hashes.insert(key.key_sha256.to_ascii_lowercase()) {
+                errors.push(format!("auth.keys.{name}.key_sha256 is shared with another key"));
+            }
+            if key.scopes.is_empty() {
+                errors.push(format!("auth.keys.{name}.scopes must not be empty"));
+            }
+            if let Some(rate_limit) = key.rate_limit {
+                if !rate_limit.per_second.is_finite() || rate_limit.per_second <= 0.0 || rate_limit.burst == 0 {
+                    errors.push(format!("auth.keys.{name}.rate_limit per_second and burst must be greater than 0"));
+                }
+            }
+            if let Some(quota) = key.quota {
+                if quota.requests == 0 || quota.period == 0 {
+                    errors.push(format!("auth.keys.{name}.quota requests and period must be greater than 0"));
+                }
+            }
+        }
+    }
+}
+
+impl Scope {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scope::Search => { "search" }
+            Scope::Admin => { "admin" }
+            Scope::Write => { "write" }
+        }
+    }
+}
+
+impl ApiKeyConfig {
+    pub fn new(name: String, key_sha256: String, scopes: Vec<Scope>, rate_limit: Option<RateLimitConfig>, quota: Option<QuotaConfig>) -> Self {
+        Self { name, key_sha256, scopes, rate_limit, quota }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn key_sha256(&self) -> &str {
+        &self.key_sha256
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimitConfig> {
+        self.rate_limit
+    }
+
+    pub fn quota(&self) -> Option<QuotaConfig> {
+        self.quota
+    }
+}
+
+impl RateLimitConfig {
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        Self { per_second, burst }
+    }
+
+    pub fn per_second(&self) -> f64 {
+        self.per_second
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+impl QuotaConfig {
+    pub fn new(requests: u64, period: u64) -> Self {
+        Self { requests, period }
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+}
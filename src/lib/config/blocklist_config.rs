@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Opt-in blocklist of taken down documents, removed from the live indexes as soon as they are blocked.
+#[derive(Deserialize, Serialize)]
+pub struct BlocklistConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    store: BlocklistStoreConfig,
+    #[serde(default = "default_reload_interval")]
+    reload_interval: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum BlocklistStoreConfig {
+    /// `blocklist.blocked_ids` table, created on the first access.
+    #[default]
+    Database,
+    /// JSON array of blocked ids, created on the first id blocked through the admin endpoints.
+    File {
+        path: String,
+    },
+}
+
+fn default_reload_interval() -> u64 {
+    30
+}
+
+impl Default for BlocklistConfig {
+    fn default() -> Self {
+        Self { enabled: false, store: BlocklistStoreConfig::default(), reload_interval: default_reload_interval() }
+    }
+}
+
+impl BlocklistConfig {
+    pub fn new(enabled: bool, store: BlocklistStoreConfig, reload_interval: u64) -> Self {
+        Self { enabled, store, reload_interval }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn store(&self) -> &BlocklistStoreConfig {
+        &self.store
+    }
+
+    /// Seconds between reloads of the store, removing the ids blocked through other instances.
+    pub fn reload_interval(&self) -> u64 {
+        self.reload_interval
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if !self.enabled {
+            return;
+        }
+        if self.reload_interval == 0 {
+            errors.push("blocklist.reload_interval must be greater than 0".to_string());
+        }
+        if let BlocklistStoreConfig::File { path } = &self.store {
+            let parent = Path::new(path).parent().filter(|parent| !parent.as_os_str().is_empty());
+            if path.is_empty() || parent.is_some_and(|parent| !parent.is_dir()) {
+                errors.push(format!("blocklist.store.path '{path}' is not in an existing directory"));
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// Opt-in subscription to a Postgres notification channel, applying document changes to the live
+/// indexes between full builds. The listener holds one connection of the database pool.
+#[derive(Deserialize, Serialize)]
+pub struct ChangeFeedConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_channel")]
+    channel: String,
+    #[serde(default = "default_reconnect_delay")]
+    reconnect_delay: u64,
+    #[serde(default = "default_catch_up_interval")]
+    catch_up_interval: u64,
+}
+
+fn default_channel() -> String {
+    "content_changes".to_string()
+}
+
+fn default_reconnect_delay() -> u64 {
+    5
+}
+
+fn default_catch_up_interval() -> u64 {
+    300
+}
+
+impl Default for ChangeFeedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channel: default_channel(),
+            reconnect_delay: default_reconnect_delay(),
+            catch_up_interval: default_catch_up_interval(),
+        }
+    }
+}
+
+impl ChangeFeedConfig {
+    pub fn new(enabled: bool, channel: String, reconnect_delay: u64, catch_up_interval: u64) -> Self {
+        Self { enabled, channel, reconnect_delay, catch_up_interval }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Channel the change events are sent to with `pg_notify`.
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// Seconds to wait before reconnecting after the listener connection failed.
+    pub fn reconnect_delay(&self) -> u64 {
+        self.reconnect_delay
+    }
+
+    /// Minimum seconds between two full rebuilds catching up with a reconnection, a reconnection sooner
+    /// delays its rebuild so a flapping connection never rebuilds back to back. 0 rebuilds right away.
+    pub fn catch_up_interval(&self) -> u64 {
+        self.catch_up_interval
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if !self.enabled {
+            return;
+        }
+        // LISTEN takes an identifier, not a bindable parameter
+        let valid_channel = self.channel.len() <= 63
+            && self.channel.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && self.channel.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_channel {
+            errors.push(format!("change_feed.channel '{}' must be an identifier of at most 63 letters, digits or underscores", self.channel));
+        }
+        if self.reconnect_delay == 0 {
+            errors.push("change_feed.reconnect_delay must be greater than 0".to_string());
+        }
+    }
+}
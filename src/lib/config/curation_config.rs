@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Opt-in editorial rules pinning, hiding or boosting results of given queries.
+#[derive(Deserialize, Serialize)]
+pub struct CurationConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    store: CurationStoreConfig,
+    #[serde(default = "default_reload_interval")]
+    reload_interval: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CurationStoreConfig {
+    /// `curation.rules` table, created on the first access.
+    #[default]
+    Database,
+    /// JSON array of rules, created on the first rule saved through the admin endpoints.
+    File {
+        path: String,
+    },
+}
+
+fn default_reload_interval() -> u64 {
+    30
+}
+
+impl Default for CurationConfig {
+    fn default() -> Self {
+        Self { enabled: false, store: CurationStoreConfig::default(), reload_interval: default_reload_interval() }
+    }
+}
+
+impl CurationConfig {
+    pub fn new(enabled: bool, store: CurationStoreConfig, reload_interval: u64) -> Self {
+        Self { enabled, store, reload_interval }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn store(&self) -> &CurationStoreConfig {
+        &self.store
+    }
+
+    /// Seconds between reloads of the store, picking up rules edited outside of this instance.
+    pub fn reload_interval(&self) -> u64 {
+        self.reload_interval
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if !self.enabled {
+            return;
+        }
+        if self.reload_interval == 0 {
+            errors.push("curation.reload_interval must be greater than 0".to_string());
+        }
+        if let CurationStoreConfig::File { path } = &self.store {
+            let parent = Path::new(path).parent().filter(|parent| !parent.as_os_str().is_empty());
+            if path.is_empty() || parent.is_some_and(|parent| !parent.is_dir()) {
+                errors.push(format!("curation.store.path '{path}' is not in an existing directory"));
+            }
+        }
+    }
+}
@@ -48,5 +48,24 @@ impl DatabaseConfig<> {
     pub fn set_host(&mut self, host: String) {
         self.host = host;
     }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if self.host.is_empty() {
+            errors.push("database.host must not be empty".to_string());
+        }
+        if self.port == 0 {
+            errors.push("database.port must be greater than 0".to_string());
+        }
+        if self.db_name.is_empty() {
+            errors.push("database.db_name must not be empty".to_string());
+        }
+        if self.max_connections == 0 {
+            errors.push("database.max_connections must be greater than 0".to_string());
+        }
+        if self.min_connections > self.max_connections {
+            errors.push(format!("database.min_connections ({}) must be lower or equal than database.max_connections ({})"
+                                , self.min_connections, self.max_connections));
+        }
+    }
 }
 
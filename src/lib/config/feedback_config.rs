@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Opt-in click feedback, boosting the results users select for the same query.
+#[derive(Deserialize, Serialize)]
+pub struct FeedbackConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default = "default_path")]
+    path: String,
+    #[serde(default = "default_half_life_hours")]
+    half_life_hours: u64,
+    #[serde(default = "default_boost")]
+    boost: f32,
+    #[serde(default = "default_flush_interval")]
+    flush_interval: u64,
+    #[serde(default = "default_max_queries")]
+    max_queries: u64,
+}
+
+fn default_path() -> String {
+    "./data/click-feedback.json".to_string()
+}
+
+fn default_half_life_hours() -> u64 {
+    168
+}
+
+fn default_boost() -> f32 {
+    0.5
+}
+
+fn default_flush_interval() -> u64 {
+    60
+}
+
+fn default_max_queries() -> u64 {
+    100_000
+}
+
+impl Default for FeedbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_path(),
+            half_life_hours: default_half_life_hours(),
+            boost: default_boost(),
+            flush_interval: default_flush_interval(),
+            max_queries: default_max_queries(),
+        }
+    }
+}
+
+impl FeedbackConfig {
+    pub fn new(enabled: bool, path: String, half_life_hours: u64, boost: f32, flush_interval: u64, max_queries: u64) -> Self {
+        Self { enabled, path, half_life_hours, boost, flush_interval, max_queries }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// JSON file the click aggregates are loaded from at boot and saved to.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Hours after which a click counts half as much.
+    pub fn half_life_hours(&self) -> u64 {
+        self.half_life_hours
+    }
+
+    /// Weight of the clicks in the score, a result is scored `score * (1 + boost * ln(1 + clicks))`.
+    pub fn boost(&self) -> f32 {
+        self.boost
+    }
+
+    /// Seconds between saves of the click aggregates, they are also saved at shutdown.
+    pub fn flush_interval(&self) -> u64 {
+        self.flush_interval
+    }
+
+    /// Queries whose clicks are kept, the least used ones are forgotten beyond it.
+    pub fn max_queries(&self) -> u64 {
+        self.max_queries
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if !self.enabled {
+            return;
+        }
+        let parent = Path::new(&self.path).parent().filter(|parent| !parent.as_os_str().is_empty());
+        if self.path.is_empty() || parent.is_some_and(|parent| !parent.is_dir()) {
+            errors.push(format!("feedback.path '{}' is not in an existing directory", self.path));
+        }
+        if self.half_life_hours == 0 {
+            errors.push("feedback.half_life_hours must be greater than 0".to_string());
+        }
+        if !self.boost.is_finite() || self.boost < 0.0 {
+            errors.push(format!("feedback.boost must be a positive number, got {}", self.boost));
+        }
+        if self.flush_interval == 0 {
+            errors.push("feedback.flush_interval must be greater than 0".to_string());
+        }
+        if self.max_queries == 0 {
+            errors.push("feedback.max_queries must be greater than 0".to_string());
+        }
+    }
+}
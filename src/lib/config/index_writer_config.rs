@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+const BYTES_PER_MB: usize = 1_000_000;
+/// Tantivy bounds of the memory budget of every writer thread, in MB.
+const MIN_THREAD_MEMORY_BUDGET: usize = 15;
+const MAX_THREAD_MEMORY_BUDGET: usize = 4_000;
+const MAX_THREADS: usize = 8;
+
+/// Index writer settings of the builds of a single entity. Writers only live while an index is written, so
+/// the memory budget is only reserved during builds, live document writes use [`IndexWriterConfig::live`].
+#[derive(Deserialize, Serialize, Clone)]
+pub struct IndexWriterConfig {
+    #[serde(default = "default_memory_budget")]
+    memory_budget: usize,
+    threads: Option<usize>,
+    #[serde(default)]
+    merge_policy: MergePolicyConfig,
+    #[serde(default = "default_force_merge")]
+    force_merge: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum MergePolicyConfig {
+    /// Merges segments of similar sizes in the background, the Tantivy defaults being kept for unset settings.
+    Log {
+        min_num_segments: Option<usize>,
+        max_docs_before_merge: Option<usize>,
+    },
+    /// Never merges in the background, segments are only merged by `force_merge`.
+    None,
+}
+
+fn default_memory_budget() -> usize {
+    100
+}
+
+fn default_force_merge() -> bool {
+    true
+}
+
+impl Default for IndexWriterConfig {
+    fn default() -> Self {
+        Self {
+            memory_budget: default_memory_budget(),
+            threads: None,
+            merge_policy: MergePolicyConfig::default(),
+            force_merge: default_force_merge(),
+        }
+    }
+}
+
+impl Default for MergePolicyConfig {
+    fn default() -> Self {
+        MergePolicyConfig::Log { min_num_segments: None, max_docs_before_merge: None }
+    }
+}
+
+impl IndexWriterConfig {
+    pub fn new(memory_budget: usize, threads: Option<usize>, merge_policy: MergePolicyConfig, force_merge: bool) -> Self {
+        Self { memory_budget, threads, merge_policy, force_merge }
+    }
+
+    /// Settings of the writers of live upserts and deletes, which hold a few documents: a single thread with
+    /// the smallest budget, merging the small segments they leave in the background whatever the build settings.
+    pub fn live() -> Self {
+        Self::new(MIN_THREAD_MEMORY_BUDGET, Some(1), MergePolicyConfig::default(), false)
+    }
+
+    /// Memory budget in MB shared by the writer threads.
+    pub fn memory_budget(&self) -> usize {
+        self.memory_budget
+    }
+
+    pub fn memory_budget_bytes(&self) -> usize {
+        self.memory_budget * BYTES_PER_MB
+    }
+
+    /// Writer threads, `None` letting Tantivy pick one per CPU within the memory budget.
+    pub fn threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    pub fn merge_policy(&self) -> &MergePolicyConfig {
+        &self.merge_policy
+    }
+
+    /// Whether every full build ends merging the index into a single segment, which makes searches faster.
+    pub fn force_merge(&self) -> bool {
+        self.force_merge
+    }
+
+    pub fn validate(&self, key: &str, errors: &mut Vec<String>) {
+        let threads = self.threads.unwrap_or(1);
+        if !(1..=MAX_THREADS).contains(&threads) {
+            errors.push(format!("indexer_runner.writers.{key}.threads must be between 1 and {MAX_THREADS}"));
+        } else if self.memory_budget / threads < MIN_THREAD_MEMORY_BUDGET {
+            errors.push(format!("indexer_runner.writers.{key}.memory_budget must be at least {MIN_THREAD_MEMORY_BUDGET} MB per thread"));
+        } else if self.threads.is_some() && self.memory_budget / threads > MAX_THREAD_MEMORY_BUDGET {
+            errors.push(format!("indexer_runner.writers.{key}.memory_budget must be at most {MAX_THREAD_MEMORY_BUDGET} MB per thread"));
+        }
+
+        if let MergePolicyConfig::Log { min_num_segments, max_docs_before_merge } = self.merge_policy {
+            if min_num_segments.is_some_and(|min_num_segments| min_num_segments < 2) {
+                errors.push(format!("indexer_runner.writers.{key}.merge_policy.min_num_segments must be at least 2"));
+            }
+            if max_docs_before_merge == Some(0) {
+                errors.push(format!("indexer_runner.writers.{key}.merge_policy.max_docs_before_merge must be greater than 0"));
+            }
+        }
+    }
+}
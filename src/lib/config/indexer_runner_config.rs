@@ -1,10 +1,43 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::config::index_writer_config::IndexWriterConfig;
+use crate::models::entity::Entity;
+use crate::services::index_schedule::IndexSchedule;
+
 #[derive(Deserialize, Serialize)]
 pub struct IndexerRunnerConfig {
     batch_size: u64,
     interval: u64,
-    wait_until_index: bool
+    wait_until_index: bool,
+    #[serde(default = "default_retry_interval")]
+    retry_interval: u64,
+    #[serde(default)]
+    schedules: HashMap<String, ScheduleConfig>,
+    #[serde(default)]
+    writers: HashMap<String, IndexWriterConfig>,
+}
+
+/// Schedule of a single entity, either a cron expression or a fixed interval in seconds.
+#[derive(Deserialize, Serialize, Clone, Default)]
+pub struct ScheduleConfig {
+    cron: Option<String>,
+    interval: Option<u64>,
+    #[serde(default)]
+    jitter: u64,
+    blackout: Option<BlackoutConfig>,
+}
+
+/// UTC hours range `[start, end)` in which no reindex is started, wraps around midnight when `start > end`.
+#[derive(Deserialize, Serialize, Clone, Copy)]
+pub struct BlackoutConfig {
+    start: u32,
+    end: u32,
+}
+
+fn default_retry_interval() -> u64 {
+    30
 }
 
 impl IndexerRunnerConfig {
@@ -19,4 +52,92 @@ impl IndexerRunnerConfig {
     pub fn wait_until_index(&self) -> bool {
         self.wait_until_index
     }
-}
\ No newline at end of file
+
+    /// Seconds between attempts of an initial build that failed, e.g. while the database is down.
+    pub fn retry_interval(&self) -> u64 {
+        self.retry_interval
+    }
+
+    pub fn schedule(&self, entity: Entity) -> ScheduleConfig {
+        let key: &str = entity.into();
+        let mut schedule = self.schedules.get(&key.to_ascii_lowercase())
+            .cloned()
+            .unwrap_or_default();
+
+        if schedule.cron.is_none() && schedule.interval.is_none() {
+            schedule.interval = Some(self.interval);
+        }
+        schedule
+    }
+
+    pub fn writer(&self, entity: Entity) -> IndexWriterConfig {
+        let key: &str = entity.into();
+        self.writers.get(&key.to_ascii_lowercase()).cloned().unwrap_or_default()
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if self.batch_size == 0 {
+            errors.push("indexer_runner.batch_size must be greater than 0".to_string());
+        }
+        if self.interval == 0 {
+            errors.push("indexer_runner.interval must be greater than 0".to_string());
+        }
+        if self.retry_interval == 0 {
+            errors.push("indexer_runner.retry_interval must be greater than 0".to_string());
+        }
+
+        for key in self.schedules.keys() {
+            if Entity::try_from(key.to_ascii_uppercase().as_str()).is_err() {
+                errors.push(format!("indexer_runner.schedules.{key} does not match any content type"));
+            }
+        }
+        for (key, writer) in self.writers.iter() {
+            if Entity::try_from(key.to_ascii_uppercase().as_str()).is_err() {
+                errors.push(format!("indexer_runner.writers.{key} does not match any content type"));
+            }
+            writer.validate(key, errors);
+        }
+        for entity in Entity::all() {
+            if let Err(err) = IndexSchedule::new(&self.schedule(entity)) {
+                let key: &str = entity.into();
+                errors.push(format!("indexer_runner.schedules.{}: {err}", key.to_ascii_lowercase()));
+            }
+        }
+    }
+}
+
+impl ScheduleConfig {
+    pub fn new(cron: Option<String>, interval: Option<u64>, jitter: u64, blackout: Option<BlackoutConfig>) -> Self {
+        Self { cron, interval, jitter, blackout }
+    }
+
+    pub fn cron(&self) -> Option<&str> {
+        self.cron.as_deref()
+    }
+
+    pub fn interval(&self) -> Option<u64> {
+        self.interval
+    }
+
+    pub fn jitter(&self) -> u64 {
+        self.jitter
+    }
+
+    pub fn blackout(&self) -> Option<BlackoutConfig> {
+        self.blackout
+    }
+}
+
+impl BlackoutConfig {
+    pub fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+}
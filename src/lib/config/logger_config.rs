@@ -1,10 +1,27 @@
+use std::str::FromStr;
+
 use log::LevelFilter;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 #[derive(Deserialize, Serialize)]
 pub struct LoggerConfig {
     enabled: bool,
     level: String,
+    #[serde(default)]
+    format: LogFormat,
+    #[serde(default)]
+    otlp_endpoint: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human readable lines.
+    #[default]
+    Text,
+    /// One JSON object per event, including the fields of its spans.
+    Json,
 }
 
 impl LoggerConfig {
@@ -13,14 +30,32 @@ impl LoggerConfig {
     }
 
 
+    /// Configured level, case-insensitive. Falls back to `INFO` for configurations that skipped validation.
     pub fn level(&self) -> LevelFilter {
-        match self.level.as_str() {
-            "TRACE" => LevelFilter::Trace,
-            "DEBUG" => LevelFilter::Debug,
-            "INFO" => LevelFilter::Info,
-            "ERROR" => LevelFilter::Error,
-            "WARN" => LevelFilter::Warn,
-            _ => { panic!("Log level filter is invalid, check for errors") }
+        LevelFilter::from_str(&self.level).unwrap_or(LevelFilter::Info)
+    }
+
+    pub fn format(&self) -> LogFormat {
+        self.format
+    }
+
+    /// OTLP/HTTP collector traces are exported to, e.g. `http://localhost:4318/v1/traces`.
+    /// Only honoured by builds with the `otlp` feature.
+    pub fn otlp_endpoint(&self) -> Option<&str> {
+        self.otlp_endpoint.as_deref()
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if LevelFilter::from_str(&self.level).is_err() {
+            errors.push(format!("logger.level '{}' is invalid, expected one of OFF, ERROR, WARN, INFO, DEBUG, TRACE", self.level));
+        }
+        if let Some(endpoint) = &self.otlp_endpoint {
+            if Url::parse(endpoint).is_err() {
+                errors.push(format!("logger.otlp_endpoint '{endpoint}' is not a valid URL"));
+            }
+            if cfg!(not(feature = "otlp")) {
+                errors.push("logger.otlp_endpoint requires a build with the otlp feature".to_string());
+            }
         }
     }
-}
\ No newline at end of file
+}
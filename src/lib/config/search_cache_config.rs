@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+pub struct SearchCacheConfig {
+    enabled: bool,
+    max_entries: u64,
+    ttl: Option<u64>,
+}
+
+impl Default for SearchCacheConfig {
+    fn default() -> Self {
+        Self { enabled: true, max_entries: 10_000, ttl: None }
+    }
+}
+
+impl SearchCacheConfig {
+    pub fn new(enabled: bool, max_entries: u64, ttl: Option<u64>) -> Self {
+        Self { enabled, max_entries, ttl }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Cached queries across every content type and language, least frequently used ones are evicted first.
+    pub fn max_entries(&self) -> u64 {
+        self.max_entries
+    }
+
+    /// Seconds a cached result lives, on top of the invalidation on every index change.
+    pub fn ttl(&self) -> Option<u64> {
+        self.ttl
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if self.enabled && self.max_entries == 0 {
+            errors.push("search_cache.max_entries must be greater than 0".to_string());
+        }
+        if self.ttl == Some(0) {
+            errors.push("search_cache.ttl must be greater than 0".to_string());
+        }
+    }
+}
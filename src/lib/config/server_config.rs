@@ -1,9 +1,25 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::language::Language;
+
 #[derive(Deserialize, Serialize)]
 pub struct ServerConfig {
     host: String,
     port: u16,
+    #[serde(default = "default_shutdown_timeout")]
+    shutdown_timeout: u64,
+    #[serde(default = "default_search_max_age")]
+    search_max_age: u64,
+    #[serde(default)]
+    fallback_language: Option<String>,
+}
+
+fn default_shutdown_timeout() -> u64 {
+    30
+}
+
+fn default_search_max_age() -> u64 {
+    60
 }
 
 impl ServerConfig {
@@ -14,4 +30,37 @@ impl ServerConfig {
     pub fn port(&self) -> u16 {
         self.port
     }
+
+    pub fn shutdown_timeout(&self) -> u64 {
+        self.shutdown_timeout
+    }
+
+    /// Seconds `GET /search` responses may be cached by browsers and CDNs.
+    pub fn search_max_age(&self) -> u64 {
+        self.search_max_age
+    }
+
+    /// Language whose index is searched when the requested language has no hits.
+    pub fn fallback_language(&self) -> Option<Language> {
+        self.fallback_language.as_deref().and_then(Language::from_tag)
+    }
+
+    pub fn set_host(&mut self, host: String) {
+        self.host = host;
+    }
+
+    pub fn set_port(&mut self, port: u16) {
+        self.port = port;
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if self.host.is_empty() {
+            errors.push("server.host must not be empty".to_string());
+        }
+        if let Some(language) = &self.fallback_language {
+            if Language::from_tag(language).is_none() {
+                errors.push(format!("server.fallback_language '{language}' is not supported"));
+            }
+        }
+    }
 }
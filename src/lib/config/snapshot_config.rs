@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::index_snapshot::DEFAULT_MAX_SNAPSHOT_BYTES;
+
+#[derive(Deserialize, Serialize)]
+pub struct SnapshotConfig {
+    import_path: Option<String>,
+    persist_path: Option<String>,
+    #[serde(default)]
+    skip_database: bool,
+    #[serde(default = "default_max_bytes")]
+    max_bytes: u64,
+}
+
+fn default_max_bytes() -> u64 {
+    DEFAULT_MAX_SNAPSHOT_BYTES
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self { import_path: None, persist_path: None, skip_database: false, max_bytes: default_max_bytes() }
+    }
+}
+
+impl SnapshotConfig {
+    /// Directory holding `<type>_<lang>.snapshot.tar.gz` archives imported at boot.
+    pub fn import_path(&self) -> Option<&str> {
+        self.import_path.as_deref()
+    }
+
+    /// Directory where the last successful build of every index is kept, restored at boot when the
+    /// database is unavailable.
+    pub fn persist_path(&self) -> Option<&str> {
+        self.persist_path.as_deref()
+    }
+
+    /// Serve imported snapshots only, without connecting to the database nor scheduling reindexes.
+    pub fn skip_database(&self) -> bool {
+        self.skip_database
+    }
+
+    /// Largest archive, and largest size of the index files it unpacks, accepted when importing a snapshot.
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        if self.skip_database && self.import_path.is_none() {
+            errors.push("snapshot.skip_database requires snapshot.import_path".to_string());
+        }
+        if self.max_bytes == 0 {
+            errors.push("snapshot.max_bytes must be greater than 0".to_string());
+        }
+    }
+}
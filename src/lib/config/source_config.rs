@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::models::source_document::SourceDocument;
+
+/// Data source of every entity keyed by lowercase entity name, entities without one are read from the database.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct SourcesConfig {
+    sources: HashMap<String, SourceConfig>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SourceConfig {
+    #[default]
+    Database,
+    /// Documents listed inline, e.g. fixtures for demos and tests.
+    Memory {
+        #[serde(default)]
+        documents: Vec<SourceDocument>,
+    },
+    /// JSONL or CSV file with `id`, `language` and `title` fields, re-read on every reindex.
+    File {
+        path: String,
+        format: Option<FileFormat>,
+    },
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    Jsonl,
+    Csv,
+}
+
+impl SourcesConfig {
+    pub fn source(&self, entity: Entity) -> SourceConfig {
+        let key: &str = entity.into();
+        self.sources.get(&key.to_ascii_lowercase()).cloned().unwrap_or_default()
+    }
+
+    /// Whether any entity is still indexed from the database.
+    pub fn uses_database(&self) -> bool {
+        Entity::all().into_iter().any(|entity| matches!(self.source(entity), SourceConfig::Database))
+    }
+
+    pub fn validate(&self, errors: &mut Vec<String>) {
+        for (key, source) in self.sources.iter() {
+            if Entity::try_from(key.to_ascii_uppercase().as_str()).is_err() {
+                errors.push(format!("sources.{key} does not match any content type"));
+            }
+
+            match source {
+                SourceConfig::Database => {}
+                SourceConfig::Memory { documents } => {
+                    for document in documents {
+                        if Language::try_from(document.language()).is_err() {
+                            errors.push(format!("sources.{key}.documents: unsupported language '{}' of document {}", document.language(), document.id()));
+                        }
+                    }
+                }
+                SourceConfig::File { path, format } => {
+                    if !Path::new(path).is_file() {
+                        errors.push(format!("sources.{key}.path '{path}' is not a file"));
+                    }
+                    if format.is_none() && FileFormat::from_path(path).is_none() {
+                        errors.push(format!("sources.{key}.format is required for '{path}', expected jsonl or csv"));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl FileFormat {
+    /// Format guessed from the `.jsonl` or `.csv` extension.
+    pub fn from_path(path: &str) -> Option<Self> {
+        match Path::new(path).extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "jsonl" => Some(FileFormat::Jsonl),
+            "csv" => Some(FileFormat::Csv),
+            _ => None,
+        }
+    }
+}
@@ -1,3 +1,10 @@
 pub mod search_handler;
+pub mod health_handler;
+pub mod metrics_handler;
+pub mod admin_handler;
+pub mod feedback_handler;
+pub mod document_handler;
+pub mod auth_middleware;
+pub mod language_negotiation;
 pub mod responses;
 pub mod requests;
\ No newline at end of file
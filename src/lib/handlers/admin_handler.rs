@@ -0,0 +1,176 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::Json;
+use uuid::Uuid;
+
+use crate::handlers::requests::analytics_report_query::AnalyticsReportQuery;
+use crate::handlers::requests::block_ids_request::BlockIdsRequest;
+use crate::handlers::requests::curation_rule_request::CurationRuleRequest;
+use crate::handlers::responses::blocked_id_response::BlockedIdResponse;
+use crate::handlers::responses::client_usage_response::ClientUsageResponse;
+use crate::handlers::responses::curation_rule_response::CurationRuleResponse;
+use crate::handlers::responses::problem_details::ProblemDetails;
+use crate::handlers::responses::query_report_response::QueryReportResponse;
+use crate::services::analytics_service_impl::AnalyticsService;
+use crate::models::entity::Entity;
+use crate::models::search_error::SearchError;
+use crate::services::api_key_service_impl::ApiKeyService;
+use crate::services::blocklist_service_impl::BlocklistService;
+use crate::services::curation_service_impl::CurationService;
+
+/// Remaining rate limit tokens and quota of every configured API client.
+pub async fn clients<S>(State(api_key_service): State<Arc<S>>) -> Json<Vec<ClientUsageResponse>>
+where
+    S: ApiKeyService,
+{
+    Json(api_key_service.usage().iter().map(ClientUsageResponse::from).collect())
+}
+
+/// Most searched and most searched zero-result queries, for relevance tuning.
+pub async fn analytics_report<S>(State(analytics_service): State<Arc<S>>
+                                 , query: Option<Query<AnalyticsReportQuery>>) -> Result<Json<QueryReportResponse>, ProblemDetails>
+where
+    S: AnalyticsService,
+{
+    if !analytics_service.enabled() {
+        return Err(ProblemDetails::from(&SearchError::AnalyticsDisabled));
+    }
+
+    let Query(query) = query.unwrap_or_default();
+    let period = Duration::from_secs(query.hours() * 3600);
+    let report = analytics_service.report(period, query.limit()).await
+        .map_err(|err| {
+            log::error!("failed building analytics report: {err:?}");
+            ProblemDetails::new(StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", "unexpected error building the report".to_string(), None)
+        })?;
+    Ok(Json(QueryReportResponse::from(&report)))
+}
+
+/// Every curation rule currently served.
+pub async fn list_rules<S>(State(curation_service): State<Arc<S>>) -> Result<Json<Vec<CurationRuleResponse>>, ProblemDetails>
+where
+    S: CurationService,
+{
+    if !curation_service.enabled() {
+        return Err(curation_disabled());
+    }
+    Ok(Json(curation_service.rules().iter().map(CurationRuleResponse::from).collect()))
+}
+
+/// Creates a curation rule under a generated id.
+pub async fn create_rule<S>(State(curation_service): State<Arc<S>>
+                            , payload: Result<Json<CurationRuleRequest>, JsonRejection>) -> Result<(StatusCode, Json<CurationRuleResponse>), ProblemDetails>
+where
+    S: CurationService,
+{
+    if !curation_service.enabled() {
+        return Err(curation_disabled());
+    }
+    let rule = save_rule(curation_service.as_ref(), Uuid::new_v4().to_string(), payload).await?;
+    Ok((StatusCode::CREATED, rule))
+}
+
+/// Creates or replaces the curation rule `id`.
+pub async fn put_rule<S>(State(curation_service): State<Arc<S>>
+                         , Path(id): Path<String>
+                         , payload: Result<Json<CurationRuleRequest>, JsonRejection>) -> Result<Json<CurationRuleResponse>, ProblemDetails>
+where
+    S: CurationService,
+{
+    if !curation_service.enabled() {
+        return Err(curation_disabled());
+    }
+    save_rule(curation_service.as_ref(), id, payload).await
+}
+
+pub async fn delete_rule<S>(State(curation_service): State<Arc<S>>, Path(id): Path<String>) -> Result<StatusCode, ProblemDetails>
+where
+    S: CurationService,
+{
+    if !curation_service.enabled() {
+        return Err(curation_disabled());
+    }
+    match curation_service.delete(&id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ProblemDetails::new(StatusCode::NOT_FOUND, "RULE_NOT_FOUND", format!("no curation rule '{id}'"), None)),
+        Err(err) => Err(store_problem(err, "deleting curation rule")),
+    }
+}
+
+async fn save_rule<S>(curation_service: &S, id: String, payload: Result<Json<CurationRuleRequest>, JsonRejection>) -> Result<Json<CurationRuleResponse>, ProblemDetails>
+where
+    S: CurationService,
+{
+    let Json(request) = payload.map_err(|err| ProblemDetails::from(&SearchError::InvalidPayload(err.body_text())))?;
+    let rule = curation_service.save(request.into_rule(id)).await.map_err(|err| store_problem(err, "storing curation rule"))?;
+    Ok(Json(CurationRuleResponse::from(&rule)))
+}
+
+/// Every blocked document.
+pub async fn list_blocked<S>(State(blocklist_service): State<Arc<S>>) -> Result<Json<Vec<BlockedIdResponse>>, ProblemDetails>
+where
+    S: BlocklistService,
+{
+    if !blocklist_service.enabled() {
+        return Err(blocklist_disabled());
+    }
+    Ok(Json(blocklist_service.entries().iter().map(BlockedIdResponse::from).collect()))
+}
+
+/// Blocks documents, removing them from search right away.
+pub async fn block<S>(State(blocklist_service): State<Arc<S>>
+                      , payload: Result<Json<BlockIdsRequest>, JsonRejection>) -> Result<(StatusCode, Json<Vec<BlockedIdResponse>>), ProblemDetails>
+where
+    S: BlocklistService,
+{
+    if !blocklist_service.enabled() {
+        return Err(blocklist_disabled());
+    }
+
+    let Json(request) = payload.map_err(|err| ProblemDetails::from(&SearchError::InvalidPayload(err.body_text())))?;
+    let entity = parse_entity(request.entity()).map_err(|err| ProblemDetails::from(&err))?;
+    let blocked = blocklist_service.block(entity, request.ids(), request.reason().map(str::to_string)).await
+        .map_err(|err| store_problem(err, "blocking ids"))?;
+    log::info!("blocked {} {} documents", blocked.len(), <&str>::from(entity));
+    Ok((StatusCode::CREATED, Json(blocked.iter().map(BlockedIdResponse::from).collect())))
+}
+
+/// Unblocks a document, searchable again once its index is rebuilt.
+pub async fn unblock<S>(State(blocklist_service): State<Arc<S>>, Path((entity, id)): Path<(String, u64)>) -> Result<StatusCode, ProblemDetails>
+where
+    S: BlocklistService,
+{
+    if !blocklist_service.enabled() {
+        return Err(blocklist_disabled());
+    }
+
+    let parsed = parse_entity(&entity).map_err(|err| ProblemDetails::from(&err))?;
+    match blocklist_service.unblock(parsed, id).await {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ProblemDetails::new(StatusCode::NOT_FOUND, "NOT_BLOCKED", format!("{entity} {id} is not blocked"), None)),
+        Err(err) => Err(store_problem(err, "unblocking id")),
+    }
+}
+
+fn parse_entity(entity: &str) -> Result<Entity, SearchError> {
+    Entity::try_from(entity.to_ascii_uppercase().as_str()).map_err(|_| SearchError::UnknownEntity(entity.to_string()))
+}
+
+fn blocklist_disabled() -> ProblemDetails {
+    ProblemDetails::from(&SearchError::BlocklistDisabled)
+}
+
+fn curation_disabled() -> ProblemDetails {
+    ProblemDetails::from(&SearchError::CurationDisabled)
+}
+
+fn store_problem(err: SearchError, action: &str) -> ProblemDetails {
+    if !err.is_client_error() {
+        log::error!("failed {action}: {err:?}");
+    }
+    ProblemDetails::from(&err)
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::config::auth_config::Scope;
+use crate::models::auth_error::AuthError;
+use crate::services::api_key_service_impl::{ApiKeyService, ApiKeyServiceImpl};
+
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+const BEARER_PREFIX: &str = "bearer ";
+
+/// Name of the API client that sent the request, available as a request extension on authenticated routes.
+#[derive(Clone, Debug)]
+pub struct ApiClientName(String);
+
+impl ApiClientName {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+pub async fn require_search(State(api_key_service): State<Arc<ApiKeyServiceImpl>>, request: Request, next: Next) -> Result<Response, AuthError> {
+    authorize(api_key_service.as_ref(), Scope::Search, request, next).await
+}
+
+pub async fn require_admin(State(api_key_service): State<Arc<ApiKeyServiceImpl>>, request: Request, next: Next) -> Result<Response, AuthError> {
+    authorize(api_key_service.as_ref(), Scope::Admin, request, next).await
+}
+
+pub async fn require_write(State(api_key_service): State<Arc<ApiKeyServiceImpl>>, request: Request, next: Next) -> Result<Response, AuthError> {
+    authorize(api_key_service.as_ref(), Scope::Write, request, next).await
+}
+
+async fn authorize<S>(api_key_service: &S, scope: Scope, mut request: Request, next: Next) -> Result<Response, AuthError>
+where
+    S: ApiKeyService,
+{
+    if !api_key_service.enabled() {
+        // Without keys nobody can be trusted to change the live indexes or manage the service, only searches stay open
+        if scope != Scope::Search {
+            return Err(AuthError::AuthDisabled(scope));
+        }
+        return Ok(next.run(request).await);
+    }
+
+    let client = api_key_service.authorize(api_key(request.headers()), scope)?;
+    tracing::Span::current().record("client", client.as_str());
+    request.extensions_mut().insert(ApiClientName(client));
+    Ok(next.run(request).await)
+}
+
+/// Key sent as `Authorization: Bearer <key>` or in the `X-Api-Key` header.
+fn api_key(headers: &HeaderMap) -> Option<&str> {
+    let bearer = headers.get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| value.len() > BEARER_PREFIX.len() && value[..BEARER_PREFIX.len()].eq_ignore_ascii_case(BEARER_PREFIX))
+        .map(|value| value[BEARER_PREFIX.len()..].trim());
+
+    bearer.or_else(|| headers.get(API_KEY_HEADER).and_then(|value| value.to_str().ok()))
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::extract::{Path, State};
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::Json;
+use tokio::task;
+
+use crate::handlers::language_negotiation::parse_language;
+use crate::handlers::requests::document_request::DocumentRequest;
+use crate::handlers::responses::document_upsert_response::DocumentUpsertResponse;
+use crate::models::doc_details::DocDetails;
+use crate::models::entity::Entity;
+use crate::models::search_error::SearchError;
+use crate::services::document_service_impl::DocumentService;
+
+pub const MAX_UPSERT_SIZE: usize = 1000;
+
+/// Adds or replaces documents in a live index, searchable as soon as the call returns.
+/// The next full build of the entity replaces them with its data source content.
+pub async fn upsert<D>(State(document_service): State<Arc<D>>
+                       , Path((entity, lang)): Path<(String, String)>
+                       , payload: Result<Json<Vec<DocumentRequest>>, JsonRejection>) -> Result<Json<DocumentUpsertResponse>, SearchError>
+where
+    D: DocumentService + Send + Sync + 'static,
+{
+    let entity = parse_entity(&entity)?;
+    let language = parse_language(&lang)?;
+    let Json(documents) = payload.map_err(|err| SearchError::InvalidPayload(err.body_text()))?;
+    if documents.len() > MAX_UPSERT_SIZE {
+        return Err(SearchError::InvalidPayload(format!("at most {MAX_UPSERT_SIZE} documents are allowed, got {}", documents.len())));
+    }
+    if let Some(document) = documents.iter().find(|document| document.title().trim().is_empty()) {
+        return Err(SearchError::InvalidPayload(format!("document {} has an empty title", document.id())));
+    }
+
+    let documents: Vec<DocDetails> = documents.into_iter().map(DocDetails::from).collect();
+    let received = documents.len();
+    // Index writes are blocking, keep them off the async workers
+    let blocked = task::spawn_blocking(move || document_service.upsert(entity, language, documents)).await
+        .map_err(|err| SearchError::Internal(anyhow!("document upsert panicked: {err}")))??;
+    log::info!("upserted {} {} documents in {}", received - blocked.len(), <&str>::from(entity), <&str>::from(language));
+    Ok(Json(DocumentUpsertResponse::new(received - blocked.len(), blocked)))
+}
+
+/// Deletes a document from a live index until the next full build, which adds it back if still in the data source.
+pub async fn delete<D>(State(document_service): State<Arc<D>>, Path((entity, lang, id)): Path<(String, String, u64)>) -> Result<StatusCode, SearchError>
+where
+    D: DocumentService + Send + Sync + 'static,
+{
+    let entity = parse_entity(&entity)?;
+    let language = parse_language(&lang)?;
+    task::spawn_blocking(move || document_service.delete(entity, language, &[id])).await
+        .map_err(|err| SearchError::Internal(anyhow!("document delete panicked: {err}")))??;
+    log::info!("deleted {} document {id} in {}", <&str>::from(entity), <&str>::from(language));
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn parse_entity(entity: &str) -> Result<Entity, SearchError> {
+    Entity::try_from(entity.to_ascii_uppercase().as_str()).map_err(|_| SearchError::UnknownEntity(entity.to_string()))
+}
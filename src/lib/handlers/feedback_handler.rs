@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+
+use crate::handlers::language_negotiation::request_language;
+use crate::handlers::requests::click_feedback_request::ClickFeedbackRequest;
+use crate::handlers::responses::problem_details::ProblemDetails;
+use crate::models::entity::Entity;
+use crate::models::search_error::SearchError;
+use crate::services::feedback_service_impl::FeedbackService;
+
+/// Records a selected search result, boosting it in the next searches of the same query.
+pub async fn click<F>(State(feedback_service): State<Arc<F>>
+                      , headers: HeaderMap
+                      , payload: Result<Json<ClickFeedbackRequest>, JsonRejection>) -> Result<StatusCode, ProblemDetails>
+where
+    F: FeedbackService,
+{
+    if !feedback_service.enabled() {
+        return Err(ProblemDetails::from(&SearchError::FeedbackDisabled));
+    }
+
+    let Json(request) = payload.map_err(|err| ProblemDetails::from(&SearchError::InvalidPayload(err.body_text())))?;
+    tracing::debug!(?request, "received click feedback");
+
+    let language = request_language(request.lang(), &headers).map_err(|err| ProblemDetails::from(&err))?;
+    let entity = parse_entity(request.entity()).map_err(|err| ProblemDetails::from(&err))?;
+    feedback_service.record_click(entity, language, request.keywords(), request.id())
+        .map_err(|err| ProblemDetails::from(&err))?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn parse_entity(entity: &str) -> Result<Entity, SearchError> {
+    Entity::try_from(entity.to_ascii_uppercase().as_str()).map_err(|_| SearchError::UnknownEntity(entity.to_string()))
+}
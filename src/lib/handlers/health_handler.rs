@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+
+use crate::handlers::responses::health_response::HealthResponse;
+use crate::services::health_service_impl::HealthService;
+
+pub async fn live() -> StatusCode {
+    StatusCode::OK
+}
+
+pub async fn ready<S>(State(health_service): State<Arc<S>>) -> (StatusCode, Json<HealthResponse>)
+where
+    S: HealthService,
+{
+    let readiness = health_service.readiness().await;
+    let status_code = if readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status_code, Json(HealthResponse::from(&readiness)))
+}
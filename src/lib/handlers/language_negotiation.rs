@@ -0,0 +1,56 @@
+use axum::http::{header, HeaderMap};
+
+use crate::models::language::Language;
+use crate::models::search_error::SearchError;
+
+pub const LANGUAGE_HEADER: &str = "Language";
+pub const DEFAULT_LANGUAGE: Language = Language::En;
+
+/// Language to search in, taken from the first of: `explicit` (the request body or query), the
+/// legacy `Language` header, `Accept-Language` and `DEFAULT_LANGUAGE`.
+/// Explicit languages must be supported, while `Accept-Language` is only a preference.
+pub fn request_language(explicit: Option<&str>, headers: &HeaderMap) -> Result<Language, SearchError> {
+    if let Some(language) = explicit {
+        return parse_language(language);
+    }
+    if let Some(value) = headers.get(LANGUAGE_HEADER) {
+        let language = value.to_str()
+            .map_err(|_| SearchError::InvalidLanguage(String::from_utf8_lossy(value.as_bytes()).to_string()))?;
+        return parse_language(language);
+    }
+
+    let accepted = headers.get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(accepted_language);
+    Ok(accepted.unwrap_or(DEFAULT_LANGUAGE))
+}
+
+/// Supported language with the highest quality in an `Accept-Language` header, the first listed
+/// winning ties. Ranges with `q=0` or a malformed quality are ignored, `*` matches `DEFAULT_LANGUAGE`.
+pub fn accepted_language(accept_language: &str) -> Option<Language> {
+    let mut best: Option<(Language, f32)> = None;
+    for range in accept_language.split(',') {
+        let mut params = range.split(';');
+        let tag = params.next().unwrap_or_default().trim();
+        let quality = match params.find_map(|param| param.trim().strip_prefix("q=")) {
+            Some(quality) => match quality.trim().parse::<f32>() {
+                Ok(quality) if (0.0..=1.0).contains(&quality) => quality,
+                _ => continue,
+            },
+            None => 1.0,
+        };
+        if quality <= 0.0 || best.is_some_and(|(_, best_quality)| quality <= best_quality) {
+            continue;
+        }
+
+        let language = if tag == "*" { Some(DEFAULT_LANGUAGE) } else { Language::from_tag(tag) };
+        if let Some(language) = language {
+            best = Some((language, quality));
+        }
+    }
+    best.map(|(language, _)| language)
+}
+
+pub fn parse_language(language: &str) -> Result<Language, SearchError> {
+    Language::from_tag(language).ok_or_else(|| SearchError::InvalidLanguage(language.to_string()))
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use prometheus::TEXT_FORMAT;
+
+use crate::services::metrics_service_impl::MetricsService;
+
+pub async fn metrics<S>(State(metrics_service): State<Arc<S>>) -> Result<Response, Response>
+where
+    S: MetricsService,
+{
+    match metrics_service.render() {
+        Ok(body) => {
+            Ok(([(header::CONTENT_TYPE, TEXT_FORMAT)], body).into_response())
+        }
+        Err(err) => {
+            log::error!("failed rendering metrics: {err:?}");
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        }
+    }
+}
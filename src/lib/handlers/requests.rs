@@ -1 +1,8 @@
-pub mod search_request;
\ No newline at end of file
+pub mod search_request;
+pub mod batch_search_request;
+pub mod search_query;
+pub mod analytics_report_query;
+pub mod click_feedback_request;
+pub mod curation_rule_request;
+pub mod block_ids_request;
+pub mod document_request;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_REPORT_LIMIT: usize = 20;
+pub const DEFAULT_REPORT_HOURS: u64 = 24 * 7;
+pub const MAX_REPORT_LIMIT: usize = 1000;
+
+/// Query parameters of `GET /admin/analytics`, reporting the `limit` top queries of the last `hours`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct AnalyticsReportQuery {
+    limit: Option<usize>,
+    hours: Option<u64>,
+}
+
+impl AnalyticsReportQuery {
+    pub fn new(limit: Option<usize>, hours: Option<u64>) -> Self {
+        Self { limit, hours }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_REPORT_LIMIT).clamp(1, MAX_REPORT_LIMIT)
+    }
+
+    pub fn hours(&self) -> u64 {
+        self.hours.unwrap_or(DEFAULT_REPORT_HOURS).max(1)
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Single search of a batch, `language` falls back to the `Language` header of the batch request.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BatchSearchRequest {
+    #[serde(rename = "keyword")]
+    keywords: String,
+    #[serde(rename = "type")]
+    entity: String,
+    language: Option<String>,
+    limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    filters: HashMap<String, serde_json::Value>,
+}
+
+impl BatchSearchRequest {
+    pub fn new(keywords: String, entity: String, language: Option<String>, limit: Option<usize>) -> Self {
+        Self { keywords, entity, language, limit, filters: HashMap::new() }
+    }
+
+    pub fn with_filter(mut self, name: &str, value: serde_json::Value) -> Self {
+        self.filters.insert(name.to_string(), value);
+        self
+    }
+
+    pub fn keywords(&self) -> &str {
+        &self.keywords
+    }
+
+    pub fn keywords_mut(&mut self) -> &mut str {
+        self.keywords.as_mut()
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn filters(&self) -> &HashMap<String, serde_json::Value> {
+        &self.filters
+    }
+}
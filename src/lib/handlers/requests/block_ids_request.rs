@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Body of `POST /admin/blocklist`, e.g. `{"type": "MOVIE", "ids": [42], "reason": "DMCA #1234"}`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BlockIdsRequest {
+    #[serde(rename = "type")]
+    entity: String,
+    ids: Vec<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+impl BlockIdsRequest {
+    pub fn new(entity: String, ids: Vec<u64>, reason: Option<String>) -> Self {
+        Self { entity, ids, reason }
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Result `id` selected by a user among the results of `keyword`. `lang` should be the
+/// `Content-Language` of the search response, it falls back to the `Language` and `Accept-Language` headers.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClickFeedbackRequest {
+    #[serde(rename = "keyword")]
+    keywords: String,
+    #[serde(rename = "type")]
+    entity: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lang: Option<String>,
+    id: u64,
+}
+
+impl ClickFeedbackRequest {
+    pub fn new(keywords: String, entity: String, lang: Option<String>, id: u64) -> Self {
+        Self { keywords, entity, lang, id }
+    }
+
+    pub fn keywords(&self) -> &str {
+        &self.keywords
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::curation_rule::{CurationAction, CurationRule};
+
+/// Body of `POST /admin/rules` and `PUT /admin/rules/:id`, e.g.
+/// `{"query": "matrix", "type": "MOVIE", "language": "EN", "action": "pin", "ids": [42]}`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CurationRuleRequest {
+    query: String,
+    #[serde(rename = "type")]
+    entity: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(flatten)]
+    action: CurationAction,
+}
+
+impl CurationRuleRequest {
+    pub fn new(query: String, entity: String, language: Option<String>, action: CurationAction) -> Self {
+        Self { query, entity, language, action }
+    }
+
+    pub fn into_rule(self, id: String) -> CurationRule {
+        CurationRule::new(id, self.query, self.entity, self.language, self.action)
+    }
+}
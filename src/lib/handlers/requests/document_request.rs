@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::doc_details::DocDetails;
+
+/// Item of the `PUT /documents/:type/:lang` body, e.g. `[{"id": 42, "title": "The Matrix"}]`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DocumentRequest {
+    id: u64,
+    title: String,
+}
+
+impl DocumentRequest {
+    pub fn new(id: u64, title: String) -> Self {
+        Self { id, title }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+}
+
+impl From<DocumentRequest> for DocDetails {
+    fn from(value: DocumentRequest) -> Self {
+        DocDetails::new(value.id, value.title)
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Query parameters of `GET /search`, `lang` falls back to the `Language` and `Accept-Language` headers.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchQuery {
+    q: String,
+    #[serde(rename = "type")]
+    entity: String,
+    lang: Option<String>,
+}
+
+impl SearchQuery {
+    pub fn new(q: String, entity: String, lang: Option<String>) -> Self {
+        Self { q, entity, lang }
+    }
+
+    pub fn q(&self) -> &str {
+        &self.q
+    }
+
+    pub fn q_mut(&mut self) -> &mut str {
+        self.q.as_mut()
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+}
@@ -10,6 +10,10 @@ pub struct SearchRequest<'a> {
     #[serde(borrow)]
     #[serde(rename = "type")]
     entity: Cow<'a, str>,
+    /// Takes precedence over the `Language` and `Accept-Language` headers.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    lang: Option<Cow<'a, str>>,
 }
 
 impl<'a> SearchRequest<'a> {
@@ -21,7 +25,16 @@ impl<'a> SearchRequest<'a> {
         &self.entity
     }
 
+    pub fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
     pub fn new(keywords: String, entity: Cow<'a, str>) -> Self {
-        Self { keywords, entity }
+        Self { keywords, entity, lang: None }
+    }
+
+    pub fn with_lang(mut self, lang: Cow<'a, str>) -> Self {
+        self.lang = Some(lang);
+        self
     }
 }
\ No newline at end of file
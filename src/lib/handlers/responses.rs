@@ -1 +1,9 @@
-pub mod search_response;
\ No newline at end of file
+pub mod search_response;
+pub mod health_response;
+pub mod problem_details;
+pub mod batch_search_response;
+pub mod client_usage_response;
+pub mod query_report_response;
+pub mod curation_rule_response;
+pub mod blocked_id_response;
+pub mod document_upsert_response;
\ No newline at end of file
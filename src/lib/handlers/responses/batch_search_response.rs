@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::responses::problem_details::ProblemDetails;
+use crate::models::search_error::SearchError;
+
+/// Outcome of a single search of a batch, holding either its results or its problem details.
+#[derive(Serialize, Deserialize)]
+pub struct BatchSearchResponse {
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    results: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ProblemDetails>,
+}
+
+impl BatchSearchResponse {
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn results(&self) -> Option<&[u64]> {
+        self.results.as_deref()
+    }
+
+    pub fn error(&self) -> Option<&ProblemDetails> {
+        self.error.as_ref()
+    }
+}
+
+impl From<Result<Vec<u64>, SearchError>> for BatchSearchResponse {
+    fn from(value: Result<Vec<u64>, SearchError>) -> Self {
+        match value {
+            Ok(results) => Self { status: 200, results: Some(results), error: None },
+            Err(err) => {
+                if !err.is_client_error() {
+                    log::error!("batch search failed: {err:?}");
+                }
+                let error = ProblemDetails::from(&err);
+                Self { status: error.status(), results: None, error: Some(error) }
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::blocked_id::BlockedId;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BlockedIdResponse {
+    #[serde(rename = "type")]
+    entity: String,
+    id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    blocked_at: DateTime<Utc>,
+}
+
+impl BlockedIdResponse {
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn blocked_at(&self) -> DateTime<Utc> {
+        self.blocked_at
+    }
+}
+
+impl From<&BlockedId> for BlockedIdResponse {
+    fn from(value: &BlockedId) -> Self {
+        Self {
+            entity: value.entity().to_string(),
+            id: value.id(),
+            reason: value.reason().map(str::to_string),
+            blocked_at: value.blocked_at(),
+        }
+    }
+}
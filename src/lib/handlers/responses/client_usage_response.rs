@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::auth_config::Scope;
+use crate::models::client_usage::ClientUsage;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClientUsageResponse {
+    name: String,
+    scopes: Vec<Scope>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quota_remaining: Option<u64>,
+}
+
+impl ClientUsageResponse {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn tokens(&self) -> Option<u64> {
+        self.tokens
+    }
+
+    pub fn quota_remaining(&self) -> Option<u64> {
+        self.quota_remaining
+    }
+}
+
+impl From<&ClientUsage> for ClientUsageResponse {
+    fn from(value: &ClientUsage) -> Self {
+        Self {
+            name: value.name().to_string(),
+            scopes: value.scopes().to_vec(),
+            // Requests only ever consume whole tokens
+            tokens: value.tokens().map(|tokens| tokens.floor() as u64),
+            quota_remaining: value.quota_remaining(),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::curation_rule::{CurationAction, CurationRule};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CurationRuleResponse {
+    id: String,
+    query: String,
+    #[serde(rename = "type")]
+    entity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(flatten)]
+    action: CurationAction,
+}
+
+impl CurationRuleResponse {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn action(&self) -> &CurationAction {
+        &self.action
+    }
+}
+
+impl From<&CurationRule> for CurationRuleResponse {
+    fn from(value: &CurationRule) -> Self {
+        Self {
+            id: value.id().to_string(),
+            query: value.query().to_string(),
+            entity: value.entity().to_string(),
+            language: value.language().map(str::to_string),
+            action: value.action().clone(),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DocumentUpsertResponse {
+    upserted: usize,
+    /// Ids skipped because they are in the blocklist.
+    blocked: Vec<u64>,
+}
+
+impl DocumentUpsertResponse {
+    pub fn new(upserted: usize, blocked: Vec<u64>) -> Self {
+        Self { upserted, blocked }
+    }
+
+    pub fn upserted(&self) -> usize {
+        self.upserted
+    }
+
+    pub fn blocked(&self) -> &[u64] {
+        &self.blocked
+    }
+}
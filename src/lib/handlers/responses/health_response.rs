@@ -0,0 +1,101 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::readiness::{IndexReadiness, Readiness};
+
+pub const UP_STATUS: &str = "UP";
+pub const DOWN_STATUS: &str = "DOWN";
+pub const DEGRADED_STATUS: &str = "DEGRADED";
+pub const DISABLED_STATUS: &str = "DISABLED";
+
+#[derive(Serialize, Deserialize)]
+pub struct HealthResponse {
+    status: String,
+    database: String,
+    indexes: Vec<IndexHealthResponse>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IndexHealthResponse {
+    #[serde(rename = "type")]
+    entity: String,
+    language: String,
+    status: String,
+    last_build: Option<u64>,
+    age_seconds: Option<u64>,
+    stale: bool,
+}
+
+impl HealthResponse {
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    pub fn indexes(&self) -> &[IndexHealthResponse] {
+        &self.indexes[..]
+    }
+}
+
+impl IndexHealthResponse {
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn last_build(&self) -> Option<u64> {
+        self.last_build
+    }
+
+    pub fn age_seconds(&self) -> Option<u64> {
+        self.age_seconds
+    }
+
+    pub fn stale(&self) -> bool {
+        self.stale
+    }
+}
+
+impl From<&Readiness> for HealthResponse {
+    fn from(value: &Readiness) -> Self {
+        Self {
+            status: if value.is_degraded() { DEGRADED_STATUS.to_string() } else { status(value.is_ready()) },
+            database: value.database().map(status).unwrap_or(DISABLED_STATUS.to_string()),
+            indexes: value.indexes().iter().map(IndexHealthResponse::from).collect(),
+        }
+    }
+}
+
+impl From<&IndexReadiness> for IndexHealthResponse {
+    fn from(value: &IndexReadiness) -> Self {
+        let entity: &str = value.entity().into();
+        let language: &str = value.language().into();
+        Self {
+            entity: entity.to_string(),
+            language: language.to_string(),
+            status: status(value.is_ready()),
+            last_build: value.last_build().and_then(epoch_seconds),
+            age_seconds: value.last_build().and_then(|last_build| last_build.elapsed().ok()).map(|age| age.as_secs()),
+            stale: value.stale(),
+        }
+    }
+}
+
+fn status(up: bool) -> String {
+    if up { UP_STATUS } else { DOWN_STATUS }.to_string()
+}
+
+fn epoch_seconds(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|duration| duration.as_secs())
+}
@@ -0,0 +1,120 @@
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::Json;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+
+use crate::models::auth_error::AuthError;
+use crate::models::search_error::SearchError;
+
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+const PROBLEM_TYPE_PREFIX: &str = "urn:problem-type:";
+
+/// RFC 7807 problem details body, extended with a machine readable `code` and the offending `field`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    problem_type: String,
+    title: String,
+    status: u16,
+    detail: String,
+    code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field: Option<String>,
+}
+
+impl ProblemDetails {
+    pub fn new(status: StatusCode, code: &str, detail: String, field: Option<&str>) -> Self {
+        Self {
+            problem_type: format!("{PROBLEM_TYPE_PREFIX}{}", code.to_ascii_lowercase()),
+            title: status.canonical_reason().unwrap_or_default().to_string(),
+            status: status.as_u16(),
+            detail,
+            code: code.to_string(),
+            field: field.map(str::to_string),
+        }
+    }
+
+    pub fn problem_type(&self) -> &str {
+        &self.problem_type
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, Json(self)).into_response();
+        response.headers_mut().insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE));
+        response
+    }
+}
+
+impl From<&SearchError> for ProblemDetails {
+    fn from(value: &SearchError) -> Self {
+        if value.is_disabled() {
+            return ProblemDetails::new(StatusCode::NOT_FOUND, value.code(), value.to_string(), None);
+        }
+        if value.is_client_error() {
+            return ProblemDetails::new(StatusCode::BAD_REQUEST, value.code(), value.to_string(), value.field());
+        }
+        // Internal details are logged, never leaked to clients
+        ProblemDetails::new(StatusCode::INTERNAL_SERVER_ERROR, value.code(), "unexpected error processing the search".to_string(), None)
+    }
+}
+
+impl IntoResponse for SearchError {
+    fn into_response(self) -> Response {
+        if !self.is_client_error() {
+            log::error!("search failed: {self:?}");
+        }
+        ProblemDetails::from(&self).into_response()
+    }
+}
+
+
+impl From<&AuthError> for ProblemDetails {
+    fn from(value: &AuthError) -> Self {
+        let status = match value {
+            AuthError::MissingKey | AuthError::InvalidKey => { StatusCode::UNAUTHORIZED }
+            AuthError::Forbidden(..) => { StatusCode::FORBIDDEN }
+            AuthError::RateLimited(..) | AuthError::QuotaExceeded(..) => { StatusCode::TOO_MANY_REQUESTS }
+            AuthError::AuthDisabled(..) => { StatusCode::SERVICE_UNAVAILABLE }
+        };
+        ProblemDetails::new(status, value.code(), value.to_string(), None)
+    }
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let mut response = ProblemDetails::from(&self).into_response();
+        let headers = response.headers_mut();
+        if matches!(self, AuthError::MissingKey | AuthError::InvalidKey) {
+            headers.insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        if let Some(retry_after) = self.retry_after() {
+            // Whole seconds, rounded up so clients never retry too early
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            headers.insert(header::RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+        }
+        response
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::query_report::{QueryCount, QueryReport};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryReportResponse {
+    top_queries: Vec<QueryCountResponse>,
+    top_zero_result_queries: Vec<QueryCountResponse>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryCountResponse {
+    query: String,
+    #[serde(rename = "type")]
+    entity: String,
+    language: String,
+    count: u64,
+}
+
+impl QueryReportResponse {
+    pub fn top_queries(&self) -> &[QueryCountResponse] {
+        &self.top_queries
+    }
+
+    pub fn top_zero_result_queries(&self) -> &[QueryCountResponse] {
+        &self.top_zero_result_queries
+    }
+}
+
+impl QueryCountResponse {
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl From<&QueryReport> for QueryReportResponse {
+    fn from(value: &QueryReport) -> Self {
+        Self {
+            top_queries: value.top_queries().iter().map(QueryCountResponse::from).collect(),
+            top_zero_result_queries: value.top_zero_result_queries().iter().map(QueryCountResponse::from).collect(),
+        }
+    }
+}
+
+impl From<&QueryCount> for QueryCountResponse {
+    fn from(value: &QueryCount) -> Self {
+        Self {
+            query: value.query().to_string(),
+            entity: value.entity().to_string(),
+            language: value.language().to_string(),
+            count: value.count(),
+        }
+    }
+}
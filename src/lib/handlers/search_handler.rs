@@ -1,51 +1,253 @@
-use std::sync::{Arc, LazyLock};
+use std::sync::Arc;
+use std::time::Instant;
 
-use axum::extract::State;
-use axum::http::{HeaderMap, HeaderValue, StatusCode};
-use axum::Json;
+use anyhow::anyhow;
+use axum::extract::{Query, State};
+use axum::extract::rejection::{JsonRejection, QueryRejection};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::{Extension, Json};
 use axum::response::{IntoResponse, Response};
-use axum_extra::extract::JsonDeserializer;
+use axum_extra::extract::{JsonDeserializer, JsonDeserializerRejection};
+use futures::future::join_all;
+use tokio::task;
 
+use crate::handlers::auth_middleware::{API_KEY_HEADER, ApiClientName};
+use crate::handlers::language_negotiation::{LANGUAGE_HEADER, parse_language, request_language};
+use crate::handlers::requests::batch_search_request::BatchSearchRequest;
+use crate::handlers::requests::search_query::SearchQuery;
 use crate::handlers::requests::search_request::SearchRequest;
+use crate::handlers::responses::batch_search_response::BatchSearchResponse;
+use crate::infrastructure::app_state::AppState;
 use crate::models::entity::Entity;
 use crate::models::language::Language;
+use crate::models::search_error::SearchError;
+use crate::models::search_event::SearchEvent;
+use crate::services::analytics_service_impl::AnalyticsService;
+use crate::services::index_processor::LIMIT_RESULT_SIZE;
 use crate::services::search_service_impl::SearchService;
 
-pub const LANGUAGE_HEADER: &str = "Language";
-pub const DEFAULT_LANGUAGE: &str = "EN";
-pub static DEFAULT_LANGUAGE_HEADER: LazyLock<HeaderValue> = LazyLock::new(|| {
-    HeaderValue::try_from(DEFAULT_LANGUAGE).unwrap()
-});
+pub const MAX_BATCH_SIZE: usize = 25;
 
 
-pub async fn search<S>(State(search_service): State<Arc<S>>
+pub async fn search<S>(State(app_state): State<Arc<AppState<S>>>
                        , headers: HeaderMap
-                       , payload: JsonDeserializer<SearchRequest<'_>>) -> Result<Json<Vec<u64>>, Response>
+                       , client: Option<Extension<ApiClientName>>
+                       , payload: Result<JsonDeserializer<SearchRequest<'_>>, JsonDeserializerRejection>) -> Result<Response, SearchError>
 where
     S: SearchService,
 {
-    let mut input = payload.deserialize().map_err(|err| err.into_response())?;
+    let started_at = Instant::now();
+    let payload = payload.map_err(|err| SearchError::InvalidPayload(err.body_text()))?;
+    let mut input = payload.deserialize().map_err(|err| SearchError::InvalidPayload(err.body_text()))?;
 
-    let language;
-    if let Some(value) = headers.get(LANGUAGE_HEADER) {
-        language = value.to_str().unwrap();
+    let language = request_language(input.lang(), &headers)?;
+
+    tracing::debug!(?input, language = <&str>::from(language), "received search request");
+
+    let entity = parse_entity(input.entity())?;
+
+    let keywords = input.keywords_mut();
+    let result = search_with_fallback(&app_state, keywords, language, entity, LIMIT_RESULT_SIZE);
+    let (searched, value) = observe(&app_state, entity, language, keywords, result, started_at, client.as_deref())?;
+    Ok(([(header::CONTENT_LANGUAGE, content_language(searched))], Json(value)).into_response())
+}
+
+/// Cacheable search, the ETag identifies the generation of the searched index so any reindex
+/// invalidates the cached responses.
+pub async fn search_get<S>(State(app_state): State<Arc<AppState<S>>>
+                           , headers: HeaderMap
+                           , client: Option<Extension<ApiClientName>>
+                           , query: Result<Query<SearchQuery>, QueryRejection>) -> Result<Response, SearchError>
+where
+    S: SearchService,
+{
+    let started_at = Instant::now();
+    let Query(mut query) = query.map_err(|err| SearchError::InvalidPayload(err.body_text()))?;
+
+    tracing::debug!(?query, "received search query");
+
+    let entity = parse_entity(query.entity())?;
+    let language = request_language(query.lang(), &headers)?;
+
+    let etag = entity_tag(&app_state, entity, language, query.q())?;
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, cache_control(&app_state)),
+        (header::VARY, vary(&app_state)),
+    ];
+    if if_none_match(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    let result = search_with_fallback(&app_state, query.q_mut(), language, entity, LIMIT_RESULT_SIZE);
+    let (searched, value) = observe(&app_state, entity, language, query.q(), result, started_at, client.as_deref())?;
+
+    let content_language = [(header::CONTENT_LANGUAGE, content_language(searched))];
+    // A swap during the search could mix results of the new index with the old tag
+    if entity_tag(&app_state, entity, language, query.q())? != etag {
+        return Ok(([(header::CACHE_CONTROL, "no-store")], content_language, Json(value)).into_response());
+    }
+    Ok((cache_headers, content_language, Json(value)).into_response())
+}
+
+/// Responses behind authentication must never be served by shared caches to other clients.
+fn cache_control<S>(app_state: &AppState<S>) -> String
+where
+    S: SearchService,
+{
+    let scope = if app_state.authenticated() { "private" } else { "public" };
+    format!("{scope}, max-age={}", app_state.search_max_age().as_secs())
+}
+
+fn vary<S>(app_state: &AppState<S>) -> String
+where
+    S: SearchService,
+{
+    if app_state.authenticated() {
+        format!("{LANGUAGE_HEADER}, Accept-Language, Authorization, {API_KEY_HEADER}")
     } else {
-        language = DEFAULT_LANGUAGE;
+        format!("{LANGUAGE_HEADER}, Accept-Language")
     }
+}
 
-    log::info!("received search request with language: {language}, input: {:?}", input);
+/// Runs every search of the batch concurrently and answers their results or problems in request order.
+/// Only malformed batches fail as a whole.
+pub async fn batch_search<S>(State(app_state): State<Arc<AppState<S>>>
+                             , headers: HeaderMap
+                             , client: Option<Extension<ApiClientName>>
+                             , payload: Result<Json<Vec<BatchSearchRequest>>, JsonRejection>) -> Result<Json<Vec<BatchSearchResponse>>, SearchError>
+where
+    S: SearchService + Send + Sync + 'static,
+{
+    let Json(requests) = payload.map_err(|err| SearchError::InvalidPayload(err.body_text()))?;
+    if requests.len() > MAX_BATCH_SIZE {
+        return Err(SearchError::BatchTooLarge(requests.len(), MAX_BATCH_SIZE));
+    }
+    let default_language = request_language(None, &headers)?;
 
-    let entity: Entity = Entity::try_from(input.entity().as_ref()).map_err(|_| StatusCode::BAD_REQUEST.into_response())?;
+    tracing::debug!(searches = requests.len(), "received batch search request");
 
-    let language: Language = Language::try_from(language).map_err(|_| StatusCode::BAD_REQUEST.into_response())?;
+    // Searches are CPU bound, running them on the blocking pool keeps the async workers free
+    let searches = requests.into_iter().map(|request| {
+        let app_state = app_state.clone();
+        let client = client.clone();
+        task::spawn_blocking(move || batch_item(&app_state, request, default_language, client.as_deref()))
+    });
 
-    let keywords = input.keywords_mut();
-    match search_service.search(keywords, language, entity) {
-        Ok(value) => {
-            Ok(Json(value))
+    let responses = join_all(searches).await.into_iter()
+        .map(|joined| joined.unwrap_or_else(|err| Err(SearchError::Internal(anyhow!("batch search panicked: {err}")))))
+        .map(BatchSearchResponse::from)
+        .collect();
+    Ok(Json(responses))
+}
+
+fn batch_item<S>(app_state: &AppState<S>, mut request: BatchSearchRequest, default_language: Language, client: Option<&ApiClientName>) -> Result<Vec<u64>, SearchError>
+where
+    S: SearchService,
+{
+    let started_at = Instant::now();
+    let entity = parse_entity(request.entity())?;
+    let language = match request.language() {
+        Some(language) => parse_language(language)?,
+        None => default_language,
+    };
+
+    let result = batch_limit(&request)
+        .and_then(|limit| search_with_fallback(app_state, request.keywords_mut(), language, entity, limit));
+    let (_, value) = observe(app_state, entity, language, request.keywords(), result, started_at, client)?;
+    Ok(value)
+}
+
+fn batch_limit(request: &BatchSearchRequest) -> Result<usize, SearchError> {
+    if let Some(filter) = request.filters().keys().next() {
+        return Err(SearchError::UnsupportedFilter(filter.clone()));
+    }
+    let limit = request.limit().unwrap_or(LIMIT_RESULT_SIZE);
+    if limit == 0 || limit > LIMIT_RESULT_SIZE {
+        return Err(SearchError::InvalidLimit(limit, LIMIT_RESULT_SIZE));
+    }
+    Ok(limit)
+}
+
+/// Records the search in the metrics and, once served, in the analytics log. Answers `result` unchanged.
+fn observe<S>(app_state: &AppState<S>, entity: Entity, language: Language, keywords: &str, result: Result<(Language, Vec<u64>), SearchError>, started_at: Instant, client: Option<&ApiClientName>) -> Result<(Language, Vec<u64>), SearchError>
+where
+    S: SearchService,
+{
+    let elapsed = started_at.elapsed();
+    let value = match &result {
+        Ok((_, value)) => value,
+        Err(err) => {
+            app_state.metrics().observe_search_failure(entity, language, elapsed, err.is_client_error());
+            return result;
+        }
+    };
+    app_state.metrics().observe_search(entity, language, elapsed, value.len());
+
+    let analytics = app_state.analytics();
+    if analytics.enabled() {
+        let client = client.map(|client| client.name().to_string());
+        analytics.record(SearchEvent::new(entity, language, keywords, value, analytics.top_ids(), elapsed, client));
+    }
+    result
+}
+
+/// Searches the index of `language`, then the one of the configured fallback language when the
+/// former has no hits. Answers the language whose results are returned.
+fn search_with_fallback<S>(app_state: &AppState<S>, keywords: &mut str, language: Language, entity: Entity, limit: usize) -> Result<(Language, Vec<u64>), SearchError>
+where
+    S: SearchService,
+{
+    let value = app_state.search_service().search_top(keywords, language, entity, limit)?;
+    match app_state.fallback_language() {
+        Some(fallback) if value.is_empty() && fallback != language => {
+            log::debug!("no {} hits, searching the {} index", <&str>::from(language), <&str>::from(fallback));
+            Ok((fallback, app_state.search_service().search_top(keywords, fallback, entity, limit)?))
         }
-        Err(_) => {
-            Err(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        _ => Ok((language, value)),
+    }
+}
+
+/// Tag of the generation of every index a search of `language` may read, including the fallback one,
+/// of the click feedback boosting `keywords` in each of them, and of the curation rules applied to the results.
+fn entity_tag<S>(app_state: &AppState<S>, entity: Entity, language: Language, keywords: &str) -> Result<String, SearchError>
+where
+    S: SearchService,
+{
+    let mut languages = vec![language];
+    languages.extend(app_state.fallback_language().filter(|fallback| *fallback != language));
+
+    let mut tag = <&str>::from(entity).to_ascii_lowercase();
+    for language in languages {
+        let generation = app_state.search_service().generation(language, entity)?;
+        tag.push_str(&format!("-{}-{generation}", <&str>::from(language).to_ascii_lowercase()));
+        match app_state.search_service().feedback_version(keywords, language, entity) {
+            0 => {}
+            version => tag.push_str(&format!("-f{version}")),
         }
     }
-}
\ No newline at end of file
+    match app_state.search_service().rules_fingerprint() {
+        0 => {}
+        fingerprint => tag.push_str(&format!("-r{fingerprint:x}")),
+    }
+    Ok(format!("\"{tag}\""))
+}
+
+fn content_language(language: Language) -> String {
+    <&str>::from(language).to_ascii_lowercase()
+}
+
+/// Weak comparison of `If-None-Match` against `etag`, as required for conditional GET requests.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(value) = headers.get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+    value.split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+fn parse_entity(entity: &str) -> Result<Entity, SearchError> {
+    Entity::try_from(entity).map_err(|_| SearchError::UnknownEntity(entity.to_string()))
+}
+
@@ -1,4 +1,7 @@
 pub mod http_server;
 pub mod app_state;
 pub mod di_container;
-pub mod app_runner;
\ No newline at end of file
+pub mod app_runner;
+pub mod metrics;
+pub mod shutdown;
+pub mod telemetry;
\ No newline at end of file
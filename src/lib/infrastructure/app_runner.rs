@@ -1,96 +1,368 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::{anyhow, Context};
 use sqlx::{Pool, Postgres};
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use tokio::sync::{oneshot};
 
-use crate::config::CONFIG;
-use crate::infrastructure::di_container::{DB_POOL_DEP, DIContainer, GAME_INDEX_PROCESSOR_DEP, GAME_REPOSITORY_IMPL_DEP, MOVIE_INDEX_PROCESSOR_DEP, MOVIE_REPOSITORY_IMPL_DEP, RECIPE_INDEX_PROCESSOR_DEP, RECIPE_REPOSITORY_IMPL_DEP, SEARCH_SERVICE_IMPL_DEP, TV_INDEX_PROCESSOR_DEP, TV_REPOSITORY_IMPL_DEP};
+use crate::config::analytics_config::AnalyticsSinkConfig;
+use crate::config::blocklist_config::BlocklistStoreConfig;
+use crate::config::Config;
+use crate::config::curation_config::CurationStoreConfig;
+use crate::config::source_config::{FileFormat, SourceConfig};
+use crate::infrastructure::di_container::{ANALYTICS_SERVICE_IMPL_DEP, API_KEY_SERVICE_IMPL_DEP, BLOCKED_ID_REPOSITORY_DEP, BLOCKLIST_SERVICE_IMPL_DEP, BLOCKLIST_STORE_DEP, CHANGE_FEED_SERVICE_IMPL_DEP, CONFIG_DEP, CURATION_RULE_REPOSITORY_DEP, CURATION_RULE_STORE_DEP, CURATION_SERVICE_IMPL_DEP, doc_details_retriever_dep, index_processor_dep, DB_POOL_DEP, DIContainer, DOCUMENT_SERVICE_IMPL_DEP, FEEDBACK_SERVICE_IMPL_DEP, GAME_INDEX_PROCESSOR_DEP, GAME_REPOSITORY_DEP, HEALTH_SERVICE_IMPL_DEP, METRICS_DEP, METRICS_SERVICE_IMPL_DEP, MOVIE_INDEX_PROCESSOR_DEP, MOVIE_REPOSITORY_DEP, RECIPE_INDEX_PROCESSOR_DEP, RECIPE_REPOSITORY_DEP, SEARCH_EVENT_REPOSITORY_DEP, SEARCH_EVENT_SINK_DEP, SEARCH_SERVICE_IMPL_DEP, TV_INDEX_PROCESSOR_DEP, TV_REPOSITORY_DEP};
 use crate::infrastructure::http_server::HttpServer;
+use crate::infrastructure::metrics::Metrics;
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::infrastructure::telemetry;
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::models::scored_doc::ScoredDoc;
+use crate::repositories::blocked_id_repository_impl::BlockedIdRepositoryImpl;
+use crate::repositories::curation_rule_repository_impl::CurationRuleRepositoryImpl;
 use crate::repositories::game_repository_impl::GameRepositoryImpl;
 use crate::repositories::movie_repository_impl::MovieRepositoryImpl;
 use crate::repositories::recipe_repository_impl::RecipeRepositoryImpl;
+use crate::repositories::search_event_repository_impl::SearchEventRepositoryImpl;
 use crate::repositories::tv_repository_impl::TvRepositoryImpl;
+use crate::services::analytics_service_impl::AnalyticsServiceImpl;
+use crate::services::api_key_service_impl::ApiKeyServiceImpl;
+use crate::services::blocklist_service_impl::BlocklistServiceImpl;
+use crate::services::blocklist_store::BlocklistStore;
+use crate::services::change_feed_service_impl::ChangeFeedServiceImpl;
+use crate::services::curation_rule_store::CurationRuleStore;
+use crate::services::curation_service_impl::CurationServiceImpl;
+use crate::services::doc_details_retriever::DocDetailsRetriever;
+use crate::services::document_service_impl::DocumentServiceImpl;
+use crate::services::feedback_service_impl::FeedbackServiceImpl;
+use crate::services::health_service_impl::HealthServiceImpl;
+use crate::services::impls::database_blocklist_store::DatabaseBlocklistStore;
+use crate::services::impls::database_curation_rule_store::DatabaseCurationRuleStore;
+use crate::services::impls::database_search_event_sink::DatabaseSearchEventSink;
+use crate::services::impls::file_blocklist_store::FileBlocklistStore;
+use crate::services::impls::file_curation_rule_store::FileCurationRuleStore;
+use crate::services::impls::file_doc_details_retriever::FileDocDetailsRetriever;
+use crate::services::impls::file_search_event_sink::FileSearchEventSink;
+use crate::services::impls::game_doc_details_retriever::GameDocDetailsRetriever;
+use crate::services::impls::in_memory_doc_details_retriever::InMemoryDocDetailsRetriever;
+use crate::services::impls::movie_doc_details_retriever::MovieDocDetailsRetriever;
+use crate::services::impls::recipe_doc_details_retriever::RecipeDocDetailsRetriever;
+use crate::services::impls::tv_doc_details_retriever::TvDocDetailsRetriever;
 use crate::services::index_processor::IndexProcessor;
 use crate::services::indexer_runner::IndexerRunner;
+use crate::services::metrics_service_impl::MetricsServiceImpl;
+use crate::services::search_event_sink::SearchEventSink;
 use crate::services::search_service_impl::SearchServiceImpl;
 
 pub struct AppRunner;
 
 
 impl AppRunner {
-    pub async fn run(signal: Option<oneshot::Sender<()>>) -> anyhow::Result<()> {
-        Self::logger_init();
-        let db_pool = Self::database_init().await?;
-        let di_container = Self::dependency_injection_init(db_pool)?;
-        Self::background_jobs(&di_container).await;
+    /// Runs the application until SIGINT/SIGTERM or until the [`ShutdownHandle`] sent through `signal` is triggered.
+    pub async fn run(config: Config, signal: Option<oneshot::Sender<ShutdownHandle>>) -> anyhow::Result<()> {
+        telemetry::init(config.logger())?;
+        let shutdown = ShutdownHandle::new();
+        shutdown.listen_signals();
+
+        let db_pool = Self::database_init(&config).await?;
+        let di_container = Self::dependency_injection_init(config, db_pool)?;
+        Self::snapshot_import(&di_container)?;
+        Self::index_restore(&di_container)?;
+        let indexer_runner = Self::background_jobs(&di_container, &shutdown).await?;
         let mut http_server = HttpServer::build(&di_container).await?;
 
         if let Some(signal) = signal {
-            signal.send(()).expect("Signal Sent");
+            if signal.send(shutdown.clone()).is_err() {
+                log::warn!("started signal receiver dropped before application start");
+            }
         }
 
-        http_server.start().await
+        let result = http_server.start(shutdown.clone()).await;
+        shutdown.shutdown();
+        let shutdown_result = Self::graceful_shutdown(&di_container, &indexer_runner).await;
+        telemetry::shutdown();
+        result.and(shutdown_result)
     }
 
-    async fn database_init() -> anyhow::Result<Pool<Postgres>> {
-        log::info!("initializing database connection pool...");
+    /// Rebuilds every index once from its data source and exits, without starting the http server.
+    /// When `export_dir` is set, every index is exported there as a snapshot archive.
+    pub async fn reindex(config: Config, export_dir: Option<&Path>) -> anyhow::Result<()> {
+        telemetry::init(config.logger())?;
+        let db_pool = Self::database_init(&config).await?;
+        let di_container = Self::dependency_injection_init(config, db_pool)?;
+        // Exported snapshots must never hold blocked documents
+        di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?.reload().await?;
+
+        let indexer_runner = IndexerRunner::new(ShutdownHandle::new());
+        let result = indexer_runner.run_once(&di_container, &Entity::all()).await;
+        di_container.get(DB_POOL_DEP)?.close().await;
+        telemetry::shutdown();
+        result?;
+
+        if let Some(export_dir) = export_dir {
+            for entity in Entity::all() {
+                let index_processor = di_container.get(index_processor_dep(entity))?;
+                for language in Language::all() {
+                    let path = index_processor.export_snapshot(entity, language, export_dir)?;
+                    log::info!("exported snapshot {}", path.display());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs a search against the index of a single entity, keeping titles and scores. The index is
+    /// loaded from the snapshot directory when given, otherwise built from its data source.
+    pub async fn query(config: Config, entity: Entity, language: Language, keywords: &str, snapshot_dir: Option<&Path>) -> anyhow::Result<Vec<ScoredDoc>> {
+        telemetry::init(config.logger())?;
+        let db_pool = Self::database_pool(&config, snapshot_dir.is_some() || !config.uses_database()).await?;
+        let di_container = Self::dependency_injection_init(config, db_pool)?;
+        let index_processor = di_container.get(index_processor_dep(entity))?;
+
+        if let Some(snapshot_dir) = snapshot_dir {
+            index_processor.import_snapshot(entity, language, snapshot_dir)?;
+        } else {
+            let indexer_runner = IndexerRunner::new(ShutdownHandle::new());
+            indexer_runner.run_once(&di_container, &[entity]).await?;
+            di_container.get(DB_POOL_DEP)?.close().await;
+        }
+
+        let keywords = keywords.to_ascii_lowercase();
+        let tokens = keywords.split_whitespace().collect::<Vec<&str>>();
+        Ok(index_processor.explain(language, &tokens)?)
+    }
+
+    /// Imports the configured snapshots before any reindex, failing only when the database is skipped.
+    fn snapshot_import(di_container: &DIContainer) -> anyhow::Result<()> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let Some(import_path) = config.snapshot().import_path() else {
+            return Ok(());
+        };
+
+        for entity in Entity::all() {
+            let index_processor = di_container.get(index_processor_dep(entity))?;
+            for language in Language::all() {
+                match index_processor.import_snapshot(entity, language, Path::new(import_path)) {
+                    Ok(()) => {
+                        log::info!("imported {} {} snapshot from {import_path}", <&str>::from(entity), <&str>::from(language));
+                    }
+                    Err(err) if config.snapshot().skip_database() => {
+                        return Err(err.context(format!("failed importing snapshots from {import_path}")));
+                    }
+                    Err(err) => {
+                        log::warn!("failed importing {} {} snapshot, it will be built from database: {err:?}", <&str>::from(entity), <&str>::from(language));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores the indexes persisted by a previous run, served until the database allows a rebuild.
+    fn index_restore(di_container: &DIContainer) -> anyhow::Result<()> {
+        for entity in Entity::all() {
+            let index_processor = di_container.get(index_processor_dep(entity))?;
+            for language in index_processor.restore() {
+                log::info!("restored persisted {} {} index", <&str>::from(entity), <&str>::from(language));
+            }
+        }
+        Ok(())
+    }
+
+    async fn graceful_shutdown(di_container: &DIContainer, indexer_runner: &IndexerRunner) -> anyhow::Result<()> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let timeout = Duration::from_secs(config.server().shutdown_timeout());
+        indexer_runner.stop(timeout).await;
+        di_container.get(ANALYTICS_SERVICE_IMPL_DEP)?.stop(timeout).await;
+        di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?.stop(timeout).await;
+        di_container.get(CURATION_SERVICE_IMPL_DEP)?.stop();
+        di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?.stop();
+        di_container.get(CHANGE_FEED_SERVICE_IMPL_DEP)?.stop();
+
+        log::info!("closing database connection pool...");
+        di_container.get(DB_POOL_DEP)?.close().await;
+        log::info!("shutdown completed");
+        Ok(())
+    }
+
+    /// Falls back to a lazy pool when the database is down but persisted indexes can be served meanwhile.
+    async fn database_init(config: &Config) -> anyhow::Result<Pool<Postgres>> {
+        match Self::database_pool(config, !config.uses_database()).await {
+            Err(err) if config.snapshot().persist_path().is_some() => {
+                log::warn!("database unavailable, serving persisted indexes until it recovers: {err:?}");
+                Self::database_pool(config, true).await
+            }
+            result => result,
+        }
+    }
 
-        let conn_info = format!(
-            "postgres://{}:{}@{}:{}/{}",
-            CONFIG.database().username(),
-            CONFIG.database().password(),
-            CONFIG.database().host(),
-            CONFIG.database().port(),
-            CONFIG.database().db_name()
-        );
-        let db_pool = PgPoolOptions::new()
-            .max_connections(CONFIG.database().max_connections())
-            .min_connections(CONFIG.database().min_connections())
-            .connect(conn_info.as_str())
-            .await?;
+    /// Connects the pool eagerly, or lazily when the database is not expected to be used.
+    async fn database_pool(config: &Config, lazy: bool) -> anyhow::Result<Pool<Postgres>> {
+        let connect_options = PgConnectOptions::new()
+            .host(config.database().host())
+            .port(config.database().port())
+            .username(config.database().username())
+            .password(config.database().password())
+            .database(config.database().db_name());
+        let pool_options = PgPoolOptions::new()
+            .max_connections(config.database().max_connections())
+            .min_connections(config.database().min_connections());
+
+        if lazy {
+            log::info!("database connection pool will connect lazily");
+            return Ok(pool_options.connect_lazy_with(connect_options));
+        }
+
+        log::info!("initializing database connection pool...");
+        let db_pool = pool_options.connect_with(connect_options).await?;
 
         log::info!("initialized database connection pool successfully");
 
         Ok(db_pool)
     }
 
-    fn logger_init() {
-        if CONFIG.logger().enabled() {
-            env_logger::builder()
-                .filter_level(CONFIG.logger().level())
-                .init();
-        }
+    fn dependency_injection_init(config: Config, db_pool: Pool<Postgres>) -> anyhow::Result<Arc<DIContainer>> {
+        Self::build_container(DIContainer::new(), config, db_pool)
     }
 
-    fn dependency_injection_init(db_pool: Pool<Postgres>) -> anyhow::Result<Arc<DIContainer>> {
-        let di_container = DIContainer::new();
+    /// Registers every component into `di_container` in dependency order. Components overridden in
+    /// `di_container` beforehand are kept, so tests can swap single components such as a repository.
+    pub fn build_container(di_container: Arc<DIContainer>, config: Config, db_pool: Pool<Postgres>) -> anyhow::Result<Arc<DIContainer>> {
+        // Infrastructure
+        di_container.add(CONFIG_DEP, config)?;
+        di_container.add(METRICS_DEP, Metrics::new()?)?;
 
         // Repositories
-        di_container.add(DB_POOL_DEP, db_pool);
-        di_container.add(MOVIE_REPOSITORY_IMPL_DEP, MovieRepositoryImpl::new(&di_container)?);
-        di_container.add(TV_REPOSITORY_IMPL_DEP, TvRepositoryImpl::new(&di_container)?);
-        di_container.add(RECIPE_REPOSITORY_IMPL_DEP, RecipeRepositoryImpl::new(&di_container)?);
-        di_container.add(GAME_REPOSITORY_IMPL_DEP, GameRepositoryImpl::new(&di_container)?);
+        di_container.add(DB_POOL_DEP, db_pool)?;
+        di_container.add_arc(MOVIE_REPOSITORY_DEP, Arc::new(MovieRepositoryImpl::new(&di_container)?))?;
+        di_container.add_arc(TV_REPOSITORY_DEP, Arc::new(TvRepositoryImpl::new(&di_container)?))?;
+        di_container.add_arc(RECIPE_REPOSITORY_DEP, Arc::new(RecipeRepositoryImpl::new(&di_container)?))?;
+        di_container.add_arc(GAME_REPOSITORY_DEP, Arc::new(GameRepositoryImpl::new(&di_container)?))?;
+        di_container.add_arc(SEARCH_EVENT_REPOSITORY_DEP, Arc::new(SearchEventRepositoryImpl::new(&di_container)?))?;
+        di_container.add_arc(CURATION_RULE_REPOSITORY_DEP, Arc::new(CurationRuleRepositoryImpl::new(&di_container)?))?;
+        di_container.add_arc(BLOCKED_ID_REPOSITORY_DEP, Arc::new(BlockedIdRepositoryImpl::new(&di_container)?))?;
+
+        // Data sources
+        let config = di_container.get(CONFIG_DEP)?;
+        for entity in Entity::all() {
+            di_container.add_arc(doc_details_retriever_dep(entity), Self::doc_details_retriever(&di_container, &config, entity)?)?;
+        }
+
+        // Sinks
+        if config.analytics().enabled() {
+            di_container.add_arc(SEARCH_EVENT_SINK_DEP, Self::search_event_sink(&di_container, &config)?)?;
+        }
+        if config.curation().enabled() {
+            di_container.add_arc(CURATION_RULE_STORE_DEP, Self::curation_rule_store(&di_container, &config)?)?;
+        }
+        if config.blocklist().enabled() {
+            di_container.add_arc(BLOCKLIST_STORE_DEP, Self::blocklist_store(&di_container, &config)?)?;
+        }
 
         // Indexers
-        di_container.add(MOVIE_INDEX_PROCESSOR_DEP, IndexProcessor::new()?);
-        di_container.add(TV_INDEX_PROCESSOR_DEP, IndexProcessor::new()?);
-        di_container.add(GAME_INDEX_PROCESSOR_DEP, IndexProcessor::new()?);
-        di_container.add(RECIPE_INDEX_PROCESSOR_DEP, IndexProcessor::new()?);
+        di_container.add(MOVIE_INDEX_PROCESSOR_DEP, Self::index_processor(&config, Entity::Movie)?)?;
+        di_container.add(TV_INDEX_PROCESSOR_DEP, Self::index_processor(&config, Entity::Tv)?)?;
+        di_container.add(GAME_INDEX_PROCESSOR_DEP, Self::index_processor(&config, Entity::Game)?)?;
+        di_container.add(RECIPE_INDEX_PROCESSOR_DEP, Self::index_processor(&config, Entity::Recipe)?)?;
 
         // Services
-        di_container.add(SEARCH_SERVICE_IMPL_DEP, SearchServiceImpl::new(&di_container));
+        di_container.add(FEEDBACK_SERVICE_IMPL_DEP, FeedbackServiceImpl::new(&di_container)?)?;
+        di_container.add(CURATION_SERVICE_IMPL_DEP, CurationServiceImpl::new(&di_container)?)?;
+        di_container.add(BLOCKLIST_SERVICE_IMPL_DEP, BlocklistServiceImpl::new(&di_container)?)?;
+        di_container.add(DOCUMENT_SERVICE_IMPL_DEP, DocumentServiceImpl::new(&di_container)?)?;
+        di_container.add(CHANGE_FEED_SERVICE_IMPL_DEP, ChangeFeedServiceImpl::new(&di_container)?)?;
+        di_container.add(SEARCH_SERVICE_IMPL_DEP, SearchServiceImpl::new(&di_container)?)?;
+        di_container.add(HEALTH_SERVICE_IMPL_DEP, HealthServiceImpl::new(&di_container)?)?;
+        di_container.add(METRICS_SERVICE_IMPL_DEP, MetricsServiceImpl::new(&di_container)?)?;
+        di_container.add(API_KEY_SERVICE_IMPL_DEP, ApiKeyServiceImpl::new(&di_container)?)?;
+        di_container.add(ANALYTICS_SERVICE_IMPL_DEP, AnalyticsServiceImpl::new(&di_container)?)?;
 
         Ok(di_container)
     }
 
-    async fn background_jobs(di_container: &DIContainer) {
-        let indexer_runner: IndexerRunner = Default::default();
-        let mut signal = indexer_runner.run(di_container);
+    fn doc_details_retriever(di_container: &DIContainer, config: &Config, entity: Entity) -> anyhow::Result<Arc<dyn DocDetailsRetriever>> {
+        Ok(match config.sources().source(entity) {
+            SourceConfig::Database => match entity {
+                Entity::Movie => Arc::new(MovieDocDetailsRetriever::new(di_container)?),
+                Entity::Tv => Arc::new(TvDocDetailsRetriever::new(di_container)?),
+                Entity::Recipe => Arc::new(RecipeDocDetailsRetriever::new(di_container)?),
+                Entity::Game => Arc::new(GameDocDetailsRetriever::new(di_container)?),
+            },
+            SourceConfig::Memory { documents } => Arc::new(InMemoryDocDetailsRetriever::new(documents)),
+            SourceConfig::File { path, format } => {
+                let format = format.or_else(|| FileFormat::from_path(&path))
+                    .ok_or_else(|| anyhow!("unknown format of data source {path}"))?;
+                Arc::new(FileDocDetailsRetriever::new(PathBuf::from(path), format))
+            }
+        })
+    }
+
+    fn search_event_sink(di_container: &DIContainer, config: &Config) -> anyhow::Result<Arc<dyn SearchEventSink>> {
+        Ok(match config.analytics().sink() {
+            AnalyticsSinkConfig::Database => Arc::new(DatabaseSearchEventSink::new(di_container)?),
+            AnalyticsSinkConfig::File { path, max_bytes, max_files } => {
+                Arc::new(FileSearchEventSink::new(PathBuf::from(path), *max_bytes, *max_files))
+            }
+        })
+    }
+
+    fn curation_rule_store(di_container: &DIContainer, config: &Config) -> anyhow::Result<Arc<dyn CurationRuleStore>> {
+        Ok(match config.curation().store() {
+            CurationStoreConfig::Database => Arc::new(DatabaseCurationRuleStore::new(di_container)?),
+            CurationStoreConfig::File { path } => Arc::new(FileCurationRuleStore::new(PathBuf::from(path))),
+        })
+    }
+
+    fn blocklist_store(di_container: &DIContainer, config: &Config) -> anyhow::Result<Arc<dyn BlocklistStore>> {
+        Ok(match config.blocklist().store() {
+            BlocklistStoreConfig::Database => Arc::new(DatabaseBlocklistStore::new(di_container)?),
+            BlocklistStoreConfig::File { path } => Arc::new(FileBlocklistStore::new(PathBuf::from(path))),
+        })
+    }
+
+    fn index_processor(config: &Config, entity: Entity) -> anyhow::Result<IndexProcessor> {
+        let index_processor = match config.snapshot().persist_path() {
+            Some(persist_path) => IndexProcessor::with_persistence(entity, PathBuf::from(persist_path))?,
+            None => IndexProcessor::new()?,
+        };
+        Ok(index_processor
+            .with_writer_config(config.indexer_runner().writer(entity))
+            .with_max_snapshot_bytes(config.snapshot().max_bytes()))
+    }
+
+    async fn background_jobs(di_container: &DIContainer, shutdown: &ShutdownHandle) -> anyhow::Result<Arc<IndexerRunner>> {
+        di_container.get(ANALYTICS_SERVICE_IMPL_DEP)?.start();
+        di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?.start();
+        let curation = di_container.get(CURATION_SERVICE_IMPL_DEP)?;
+        if let Err(err) = curation.reload().await {
+            log::warn!("failed loading curation rules, retrying on the next reload: {err:?}");
+        }
+        curation.start();
+        // Fatal, persisted indexes may still hold the ids blocked since they were built
+        let blocklist = di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?;
+        blocklist.reload().await.context("failed loading blocklist")?;
+        blocklist.start();
+
+        let indexer_runner = Arc::new(IndexerRunner::new(shutdown.clone()));
+        let config = di_container.get(CONFIG_DEP)?;
+        if config.snapshot().skip_database() {
+            log::info!("database skipped, serving imported snapshots without reindex");
+            return Ok(indexer_runner);
+        }
+        // Started before the first build to narrow the window of changes missed by both
+        di_container.get(CHANGE_FEED_SERVICE_IMPL_DEP)?.start(indexer_runner.clone());
+        let mut signal = indexer_runner.run(di_container)?;
 
-        if CONFIG.indexer_runner().wait_until_index() {
-            signal.recv().await;
+        if config.indexer_runner().wait_until_index() {
+            for _ in Entity::all() {
+                tokio::select! {
+                    _ = signal.recv() => {}
+                    _ = shutdown.wait() => break,
+                }
+            }
         }
+        Ok(indexer_runner)
     }
 }
\ No newline at end of file
@@ -1,15 +1,28 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use derive_builder::Builder;
 
+use crate::infrastructure::metrics::Metrics;
+use crate::models::language::Language;
+use crate::services::analytics_service_impl::AnalyticsServiceImpl;
 use crate::services::search_service_impl::SearchService;
 
 #[derive(Builder)]
+#[builder(pattern = "owned")]
 pub struct AppState<S>
 where
     S: SearchService,
 {
     search_service: Arc<S>,
+    metrics: Arc<Metrics>,
+    analytics: Arc<AnalyticsServiceImpl>,
+    search_max_age: Duration,
+    #[builder(default)]
+    fallback_language: Option<Language>,
+    /// Whether searches require an API key, their responses then being only cacheable by the client.
+    #[builder(default)]
+    authenticated: bool,
 }
 
 impl<S> AppState<S>
@@ -19,4 +32,24 @@ where
     pub fn search_service(&self) -> &Arc<S> {
         &self.search_service
     }
-}
\ No newline at end of file
+
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    pub fn analytics(&self) -> &Arc<AnalyticsServiceImpl> {
+        &self.analytics
+    }
+
+    pub fn search_max_age(&self) -> Duration {
+        self.search_max_age
+    }
+
+    pub fn fallback_language(&self) -> Option<Language> {
+        self.fallback_language
+    }
+
+    pub fn authenticated(&self) -> bool {
+        self.authenticated
+    }
+}
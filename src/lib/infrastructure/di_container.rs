@@ -1,46 +1,175 @@
-use std::any::Any;
+use std::any::{Any, type_name};
+use std::marker::PhantomData;
 use std::sync::Arc;
 
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
+use sqlx::{Pool, Postgres};
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::infrastructure::metrics::Metrics;
+use crate::models::entity::Entity;
+use crate::repositories::blocked_id_repository_impl::BlockedIdRepository;
+use crate::repositories::curation_rule_repository_impl::CurationRuleRepository;
+use crate::repositories::game_repository_impl::GameRepository;
+use crate::repositories::movie_repository_impl::MovieRepository;
+use crate::repositories::recipe_repository_impl::RecipeRepository;
+use crate::repositories::search_event_repository_impl::SearchEventRepository;
+use crate::repositories::tv_repository_impl::TvRepository;
+use crate::services::analytics_service_impl::AnalyticsServiceImpl;
+use crate::services::api_key_service_impl::ApiKeyServiceImpl;
+use crate::services::blocklist_service_impl::BlocklistServiceImpl;
+use crate::services::blocklist_store::BlocklistStore;
+use crate::services::change_feed_service_impl::ChangeFeedServiceImpl;
+use crate::services::curation_rule_store::CurationRuleStore;
+use crate::services::curation_service_impl::CurationServiceImpl;
+use crate::services::doc_details_retriever::DocDetailsRetriever;
+use crate::services::document_service_impl::DocumentServiceImpl;
+use crate::services::feedback_service_impl::FeedbackServiceImpl;
+use crate::services::health_service_impl::HealthServiceImpl;
+use crate::services::index_processor::IndexProcessor;
+use crate::services::metrics_service_impl::MetricsServiceImpl;
+use crate::services::search_event_sink::SearchEventSink;
+use crate::services::search_service_impl::SearchServiceImpl;
 
 // Repositories
-pub const DB_POOL_DEP: &str = "db_pool";
-pub const MOVIE_REPOSITORY_IMPL_DEP: &str = "movie_repository_impl";
-pub const TV_REPOSITORY_IMPL_DEP: &str = "tv_repository_impl";
-pub const RECIPE_REPOSITORY_IMPL_DEP: &str = "recipe_repository_impl";
-pub const GAME_REPOSITORY_IMPL_DEP: &str = "game_repository_impl";
+pub const DB_POOL_DEP: Key<Pool<Postgres>> = Key::new("db_pool");
+pub const MOVIE_REPOSITORY_DEP: Key<dyn MovieRepository> = Key::new("movie_repository");
+pub const TV_REPOSITORY_DEP: Key<dyn TvRepository> = Key::new("tv_repository");
+pub const RECIPE_REPOSITORY_DEP: Key<dyn RecipeRepository> = Key::new("recipe_repository");
+pub const GAME_REPOSITORY_DEP: Key<dyn GameRepository> = Key::new("game_repository");
+pub const SEARCH_EVENT_REPOSITORY_DEP: Key<dyn SearchEventRepository> = Key::new("search_event_repository");
+pub const CURATION_RULE_REPOSITORY_DEP: Key<dyn CurationRuleRepository> = Key::new("curation_rule_repository");
+pub const BLOCKED_ID_REPOSITORY_DEP: Key<dyn BlockedIdRepository> = Key::new("blocked_id_repository");
+
+// Data sources
+pub const MOVIE_DOC_DETAILS_RETRIEVER_DEP: Key<dyn DocDetailsRetriever> = Key::new("movie_doc_details_retriever");
+pub const TV_DOC_DETAILS_RETRIEVER_DEP: Key<dyn DocDetailsRetriever> = Key::new("tv_doc_details_retriever");
+pub const RECIPE_DOC_DETAILS_RETRIEVER_DEP: Key<dyn DocDetailsRetriever> = Key::new("recipe_doc_details_retriever");
+pub const GAME_DOC_DETAILS_RETRIEVER_DEP: Key<dyn DocDetailsRetriever> = Key::new("game_doc_details_retriever");
+
+// Sinks
+pub const SEARCH_EVENT_SINK_DEP: Key<dyn SearchEventSink> = Key::new("search_event_sink");
+pub const CURATION_RULE_STORE_DEP: Key<dyn CurationRuleStore> = Key::new("curation_rule_store");
+pub const BLOCKLIST_STORE_DEP: Key<dyn BlocklistStore> = Key::new("blocklist_store");
 
 // Services
-pub const SEARCH_SERVICE_IMPL_DEP: &str = "search_service_impl";
+pub const SEARCH_SERVICE_IMPL_DEP: Key<SearchServiceImpl> = Key::new("search_service_impl");
+pub const HEALTH_SERVICE_IMPL_DEP: Key<HealthServiceImpl> = Key::new("health_service_impl");
+pub const METRICS_SERVICE_IMPL_DEP: Key<MetricsServiceImpl> = Key::new("metrics_service_impl");
+pub const API_KEY_SERVICE_IMPL_DEP: Key<ApiKeyServiceImpl> = Key::new("api_key_service_impl");
+pub const ANALYTICS_SERVICE_IMPL_DEP: Key<AnalyticsServiceImpl> = Key::new("analytics_service_impl");
+pub const FEEDBACK_SERVICE_IMPL_DEP: Key<FeedbackServiceImpl> = Key::new("feedback_service_impl");
+pub const CURATION_SERVICE_IMPL_DEP: Key<CurationServiceImpl> = Key::new("curation_service_impl");
+pub const BLOCKLIST_SERVICE_IMPL_DEP: Key<BlocklistServiceImpl> = Key::new("blocklist_service_impl");
+pub const DOCUMENT_SERVICE_IMPL_DEP: Key<DocumentServiceImpl> = Key::new("document_service_impl");
+pub const CHANGE_FEED_SERVICE_IMPL_DEP: Key<ChangeFeedServiceImpl> = Key::new("change_feed_service_impl");
+
+// Infrastructure
+pub const CONFIG_DEP: Key<Config> = Key::new("config");
+pub const METRICS_DEP: Key<Metrics> = Key::new("metrics");
 
 // Indexers
-pub const MOVIE_INDEX_PROCESSOR_DEP: &str = "movie_index_processor";
-pub const TV_INDEX_PROCESSOR_DEP: &str = "tv_index_processor";
-pub const RECIPE_INDEX_PROCESSOR_DEP: &str = "recipe_index_processor";
-pub const GAME_INDEX_PROCESSOR_DEP: &str = "game_index_processor";
+pub const MOVIE_INDEX_PROCESSOR_DEP: Key<IndexProcessor> = Key::new("movie_index_processor");
+pub const TV_INDEX_PROCESSOR_DEP: Key<IndexProcessor> = Key::new("tv_index_processor");
+pub const RECIPE_INDEX_PROCESSOR_DEP: Key<IndexProcessor> = Key::new("recipe_index_processor");
+pub const GAME_INDEX_PROCESSOR_DEP: Key<IndexProcessor> = Key::new("game_index_processor");
+
+pub fn index_processor_dep(entity: Entity) -> Key<IndexProcessor> {
+    match entity {
+        Entity::Movie => { MOVIE_INDEX_PROCESSOR_DEP }
+        Entity::Tv => { TV_INDEX_PROCESSOR_DEP }
+        Entity::Recipe => { RECIPE_INDEX_PROCESSOR_DEP }
+        Entity::Game => { GAME_INDEX_PROCESSOR_DEP }
+    }
+}
+
+pub fn doc_details_retriever_dep(entity: Entity) -> Key<dyn DocDetailsRetriever> {
+    match entity {
+        Entity::Movie => { MOVIE_DOC_DETAILS_RETRIEVER_DEP }
+        Entity::Tv => { TV_DOC_DETAILS_RETRIEVER_DEP }
+        Entity::Recipe => { RECIPE_DOC_DETAILS_RETRIEVER_DEP }
+        Entity::Game => { GAME_DOC_DETAILS_RETRIEVER_DEP }
+    }
+}
+
+/// Name of a dependency together with the type registered under it, so lookups can't ask for the wrong type.
+pub struct Key<T: ?Sized> {
+    name: &'static str,
+    _type: PhantomData<fn() -> Box<T>>,
+}
+
+impl<T: ?Sized> Key<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, _type: PhantomData }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T: ?Sized> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 
+impl<T: ?Sized> Copy for Key<T> {}
 
-type GenericType = Arc<dyn Any + Send + Sync>;
+#[derive(Error, Debug)]
+pub enum DIError {
+    #[error("{0} dependency requested before being registered")]
+    NotRegistered(&'static str),
+    #[error("{0} dependency registered twice")]
+    AlreadyRegistered(&'static str),
+    #[error("{0} dependency is not a {1}")]
+    InvalidType(&'static str, &'static str),
+}
+
+/// Every dependency is stored as an `Arc<T>` boxed into `Any`, which also allows unsized trait objects.
+type GenericType = Box<dyn Any + Send + Sync>;
 pub struct DIContainer {
-    deps: DashMap<String, GenericType>,
+    deps: DashMap<&'static str, GenericType>,
+    overrides: DashSet<&'static str>,
 }
 
 impl DIContainer {
     pub fn new() -> Arc<Self> {
-        Arc::new(Self { deps: DashMap::new() })
+        Arc::new(Self { deps: DashMap::new(), overrides: DashSet::new() })
     }
 
 
-    pub fn add<T: Sync + Send + 'static>(&self, key: &str, dependency: T) {
-        self.deps.insert(key.to_string(), Arc::new(dependency) as Arc<dyn Any + Send + Sync>);
+    pub fn add<T: Send + Sync + 'static>(&self, key: Key<T>, dependency: T) -> Result<(), DIError> {
+        self.add_arc(key, Arc::new(dependency))
     }
 
-    pub fn get<T: Sync + Send + 'static>(&self, key: &str) -> Arc<T> {
-        let dep = self.deps.get(key)
-            .unwrap_or_else(|| panic!("#{key} dependency not found in dependency injection container"));
+    /// Registers a dependency, failing when the key is taken unless it was overridden, in which case
+    /// the override is kept and `dependency` is dropped.
+    pub fn add_arc<T: ?Sized + Send + Sync + 'static>(&self, key: Key<T>, dependency: Arc<T>) -> Result<(), DIError> {
+        if self.overrides.contains(key.name) {
+            log::debug!("keeping overridden {} dependency", key.name);
+            return Ok(());
+        }
+        if self.deps.contains_key(key.name) {
+            return Err(DIError::AlreadyRegistered(key.name));
+        }
+        self.deps.insert(key.name, Box::new(dependency));
+        Ok(())
+    }
 
-        dep.clone().downcast::<T>().unwrap_or_else(|_| panic!("invalid type for #{key} dependency"))
+    /// Replaces a single component before the container is built, e.g. a repository with a test double.
+    pub fn override_with<T: ?Sized + Send + Sync + 'static>(&self, key: Key<T>, dependency: Arc<T>) {
+        self.deps.insert(key.name, Box::new(dependency));
+        self.overrides.insert(key.name);
     }
-}
 
+    pub fn get<T: ?Sized + Send + Sync + 'static>(&self, key: Key<T>) -> Result<Arc<T>, DIError> {
+        let dep = self.deps.get(key.name)
+            .ok_or(DIError::NotRegistered(key.name))?;
 
+        dep.downcast_ref::<Arc<T>>()
+            .cloned()
+            .ok_or(DIError::InvalidType(key.name, type_name::<T>()))
+    }
+}
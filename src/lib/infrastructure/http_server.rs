@@ -1,13 +1,26 @@
-use axum::Router;
-use axum::routing::post;
+use std::future::IntoFuture;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use axum::{middleware, Router};
+use axum::extract::Request;
+use axum::routing::{delete, get, post, put};
 use tokio::net::TcpListener;
+use tokio::time;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
+use tracing::Span;
 
-use crate::config::CONFIG;
+use crate::config::auth_config::Scope;
 use crate::handlers;
-use crate::infrastructure::di_container::{DIContainer, SEARCH_SERVICE_IMPL_DEP};
-use crate::services::search_service_impl::SearchServiceImpl;
+use crate::infrastructure::app_state::AppStateBuilder;
+use crate::infrastructure::di_container::{ANALYTICS_SERVICE_IMPL_DEP, API_KEY_SERVICE_IMPL_DEP, BLOCKLIST_SERVICE_IMPL_DEP, CONFIG_DEP, CURATION_SERVICE_IMPL_DEP, DIContainer, DOCUMENT_SERVICE_IMPL_DEP, FEEDBACK_SERVICE_IMPL_DEP, HEALTH_SERVICE_IMPL_DEP, METRICS_DEP, METRICS_SERVICE_IMPL_DEP, SEARCH_SERVICE_IMPL_DEP};
+use crate::infrastructure::shutdown::ShutdownHandle;
 
 pub struct HttpServer {
+    shutdown_timeout: Duration,
     app: Option<Router>,
     listener: Option<TcpListener>,
     started: bool,
@@ -15,13 +28,72 @@ pub struct HttpServer {
 
 impl HttpServer {
     pub async fn build(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let app_state = AppStateBuilder::default()
+            .search_service(di_container.get(SEARCH_SERVICE_IMPL_DEP)?)
+            .metrics(di_container.get(METRICS_DEP)?)
+            .analytics(di_container.get(ANALYTICS_SERVICE_IMPL_DEP)?)
+            .search_max_age(Duration::from_secs(config.server().search_max_age()))
+            .fallback_language(config.server().fallback_language())
+            .authenticated(config.auth().enabled())
+            .build()?;
+        let api_key_service = di_container.get(API_KEY_SERVICE_IMPL_DEP)?;
+        if !config.auth().enabled() {
+            log::warn!("auth is disabled, document writes and admin routes are refused until API keys are configured");
+        } else if !config.auth().keys().iter().any(|key| key.scopes().contains(&Scope::Admin)) {
+            log::warn!("no API key has the admin scope, admin routes are refused");
+        }
+
+        // Health and metrics stay open for probes and scrapers
         let routes = Router::new()
             .route("/run", post(handlers::search_handler::search))
-            .with_state(di_container.get::<SearchServiceImpl>(SEARCH_SERVICE_IMPL_DEP));
+            .route("/run/batch", post(handlers::search_handler::batch_search))
+            .route("/search", get(handlers::search_handler::search_get))
+            .route_layer(middleware::from_fn_with_state(api_key_service.clone(), handlers::auth_middleware::require_search))
+            .with_state(Arc::new(app_state))
+            .merge(Router::new()
+                .route("/feedback/click", post(handlers::feedback_handler::click))
+                .route_layer(middleware::from_fn_with_state(api_key_service.clone(), handlers::auth_middleware::require_search))
+                .with_state(di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?))
+            .merge(Router::new()
+                .route("/admin/clients", get(handlers::admin_handler::clients))
+                .route_layer(middleware::from_fn_with_state(api_key_service.clone(), handlers::auth_middleware::require_admin))
+                .with_state(api_key_service.clone()))
+            .merge(Router::new()
+                .route("/admin/analytics", get(handlers::admin_handler::analytics_report))
+                .route_layer(middleware::from_fn_with_state(api_key_service.clone(), handlers::auth_middleware::require_admin))
+                .with_state(di_container.get(ANALYTICS_SERVICE_IMPL_DEP)?))
+            .merge(Router::new()
+                .route("/admin/rules", get(handlers::admin_handler::list_rules).post(handlers::admin_handler::create_rule))
+                .route("/admin/rules/:id", put(handlers::admin_handler::put_rule).delete(handlers::admin_handler::delete_rule))
+                .route_layer(middleware::from_fn_with_state(api_key_service.clone(), handlers::auth_middleware::require_admin))
+                .with_state(di_container.get(CURATION_SERVICE_IMPL_DEP)?))
+            .merge(Router::new()
+                .route("/admin/blocklist", get(handlers::admin_handler::list_blocked).post(handlers::admin_handler::block))
+                .route("/admin/blocklist/:type/:id", delete(handlers::admin_handler::unblock))
+                .route_layer(middleware::from_fn_with_state(api_key_service.clone(), handlers::auth_middleware::require_admin))
+                .with_state(di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?))
+            .merge(Router::new()
+                .route("/documents/:type/:lang", put(handlers::document_handler::upsert))
+                .route("/documents/:type/:lang/:id", delete(handlers::document_handler::delete))
+                .route_layer(middleware::from_fn_with_state(api_key_service, handlers::auth_middleware::require_write))
+                .with_state(di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?))
+            .merge(Router::new()
+                .route("/health/live", get(handlers::health_handler::live))
+                .route("/health/ready", get(handlers::health_handler::ready))
+                .with_state(di_container.get(HEALTH_SERVICE_IMPL_DEP)?))
+            .merge(Router::new()
+                .route("/metrics", get(handlers::metrics_handler::metrics))
+                .with_state(di_container.get(METRICS_SERVICE_IMPL_DEP)?))
+            // Outermost last: the id is set before the span is opened and echoed in the response
+            .layer(PropagateRequestIdLayer::x_request_id())
+            .layer(TraceLayer::new_for_http().make_span_with(request_span))
+            .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid));
 
-        let tcp_addr = format!("{}:{}", CONFIG.server().host(), CONFIG.server().port());
+        let tcp_addr = format!("{}:{}", config.server().host(), config.server().port());
 
         Ok(Self {
+            shutdown_timeout: Duration::from_secs(config.server().shutdown_timeout()),
             app: Some(routes),
             listener: Some(
                 TcpListener::bind(tcp_addr).await?,
@@ -29,7 +101,14 @@ impl HttpServer {
             started: false,
         })
     }
-    pub async fn start(&mut self) -> anyhow::Result<()> {
+    /// Address the server listens on, resolving the port when `server.port = 0`.
+    pub fn local_addr(&self) -> anyhow::Result<SocketAddr> {
+        let listener = self.listener.as_ref().ok_or_else(|| anyhow!("http server already started"))?;
+        Ok(listener.local_addr()?)
+    }
+
+    /// Serves until `shutdown` is triggered, then drains in-flight requests for at most the configured shutdown timeout.
+    pub async fn start(&mut self, shutdown: ShutdownHandle) -> anyhow::Result<()> {
         log::info!("http server initializing for listen incoming requests...");
         if self.started {
             return Ok(());
@@ -37,9 +116,33 @@ impl HttpServer {
         self.started = true;
         let app = self.app.take().unwrap();
         let listener = self.listener.take().unwrap();
-        axum::serve(listener, app).await.unwrap();
+
+        let server = axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown.clone().wait_owned())
+            .into_future();
+        tokio::pin!(server);
+
+        tokio::select! {
+            result = &mut server => result?,
+            _ = shutdown.wait() => {
+                log::info!("http server draining in-flight requests...");
+                match time::timeout(self.shutdown_timeout, &mut server).await {
+                    Ok(result) => result?,
+                    Err(_) => log::warn!("http server drain exceeded {}s, dropping remaining connections", self.shutdown_timeout.as_secs()),
+                }
+            }
+        }
+
+        log::info!("http server stopped");
         Ok(())
     }
 }
 
-
+/// Root span of every request, identified by the `X-Request-Id` sent by the caller or generated.
+/// `client` is recorded once the API key is authenticated.
+fn request_span(request: &Request) -> Span {
+    let request_id = request.extensions().get::<RequestId>()
+        .and_then(|request_id| request_id.header_value().to_str().ok())
+        .unwrap_or_default();
+    tracing::info_span!("http_request", method = %request.method(), path = request.uri().path(), request_id, client = tracing::field::Empty)
+}
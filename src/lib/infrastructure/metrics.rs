@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder};
+
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+
+const NAMESPACE: &str = "content_search";
+const ENTITY_LABEL: &str = "entity";
+const LANGUAGE_LABEL: &str = "language";
+const RESULT_LABEL: &str = "result";
+const CLIENT_LABEL: &str = "client";
+const CODE_LABEL: &str = "code";
+const OPERATION_LABEL: &str = "operation";
+const OUTCOME_LABEL: &str = "outcome";
+const HIT_RESULT: &str = "hit";
+const MISS_RESULT: &str = "miss";
+const OK_OUTCOME: &str = "ok";
+const CLIENT_ERROR_OUTCOME: &str = "client_error";
+const SERVER_ERROR_OUTCOME: &str = "server_error";
+const SEARCH_LATENCY_BUCKETS: &[f64] = &[0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+const SEARCH_HITS_BUCKETS: &[f64] = &[0.0, 1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 75.0];
+const INDEX_DURATION_BUCKETS: &[f64] = &[0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0];
+
+pub struct Metrics {
+    registry: Registry,
+    search_latency: HistogramVec,
+    search_hits: HistogramVec,
+    search_zero_results: IntCounterVec,
+    search_cache_requests: IntCounterVec,
+    search_cache_entries: IntGauge,
+    index_duration: HistogramVec,
+    index_documents: IntGaugeVec,
+    index_segments: IntGaugeVec,
+    index_age: IntGaugeVec,
+    index_stale: IntGaugeVec,
+    db_pool_connections: IntGauge,
+    db_pool_idle_connections: IntGauge,
+    auth_rejections: IntCounterVec,
+    analytics_dropped: IntCounter,
+    feedback_clicks: IntCounterVec,
+    documents_written: IntCounterVec,
+    change_feed_catch_ups: IntCounter,
+}
+
+impl Metrics {
+    pub fn new() -> anyhow::Result<Self> {
+        let registry = Registry::new_custom(Some(NAMESPACE.to_string()), None)?;
+        let labels = &[ENTITY_LABEL, LANGUAGE_LABEL];
+
+        let search_latency = HistogramVec::new(
+            HistogramOpts::new("search_duration_seconds", "Search request latency by outcome")
+                .buckets(SEARCH_LATENCY_BUCKETS.to_vec()), &[ENTITY_LABEL, LANGUAGE_LABEL, OUTCOME_LABEL])?;
+        let search_hits = HistogramVec::new(
+            HistogramOpts::new("search_hits", "Hits returned by successful search requests")
+                .buckets(SEARCH_HITS_BUCKETS.to_vec()), labels)?;
+        let search_zero_results = IntCounterVec::new(
+            Opts::new("search_zero_results_total", "Search requests without any hit"), labels)?;
+        let search_cache_requests = IntCounterVec::new(
+            Opts::new("search_cache_requests_total", "Search cache lookups by result"), &[ENTITY_LABEL, LANGUAGE_LABEL, RESULT_LABEL])?;
+        let search_cache_entries = IntGauge::new("search_cache_entries", "Cached search results")?;
+        let index_duration = HistogramVec::new(
+            HistogramOpts::new("index_build_duration_seconds", "Full index build duration")
+                .buckets(INDEX_DURATION_BUCKETS.to_vec()), labels)?;
+        let index_documents = IntGaugeVec::new(
+            Opts::new("index_documents", "Documents written in the last index build"), labels)?;
+        let index_segments = IntGaugeVec::new(
+            Opts::new("index_segments", "Searchable segments of the live index"), labels)?;
+        let index_age = IntGaugeVec::new(
+            Opts::new("index_age_seconds", "Seconds since the live index was built"), labels)?;
+        let index_stale = IntGaugeVec::new(
+            Opts::new("index_stale", "1 when the live index was restored from disk and not rebuilt since"), labels)?;
+        let db_pool_connections = IntGauge::new("db_pool_connections", "Open database connections")?;
+        let db_pool_idle_connections = IntGauge::new("db_pool_idle_connections", "Idle database connections")?;
+        let auth_rejections = IntCounterVec::new(
+            Opts::new("auth_rejections_total", "Requests rejected by API key authentication or rate limiting"), &[CLIENT_LABEL, CODE_LABEL])?;
+        let analytics_dropped = IntCounter::new("analytics_events_dropped_total", "Search events dropped because the analytics buffer was full")?;
+        let feedback_clicks = IntCounterVec::new(
+            Opts::new("feedback_clicks_total", "Selected search results recorded as click feedback"), labels)?;
+        let documents_written = IntCounterVec::new(
+            Opts::new("documents_written_total", "Documents upserted or deleted through the documents API"), &[ENTITY_LABEL, LANGUAGE_LABEL, OPERATION_LABEL])?;
+        let change_feed_catch_ups = IntCounter::new("change_feed_catch_ups_total", "Full rebuilds run after the change feed connection was lost")?;
+
+        registry.register(Box::new(search_latency.clone()))?;
+        registry.register(Box::new(search_hits.clone()))?;
+        registry.register(Box::new(search_zero_results.clone()))?;
+        registry.register(Box::new(search_cache_requests.clone()))?;
+        registry.register(Box::new(search_cache_entries.clone()))?;
+        registry.register(Box::new(index_duration.clone()))?;
+        registry.register(Box::new(index_documents.clone()))?;
+        registry.register(Box::new(index_segments.clone()))?;
+        registry.register(Box::new(index_age.clone()))?;
+        registry.register(Box::new(index_stale.clone()))?;
+        registry.register(Box::new(db_pool_connections.clone()))?;
+        registry.register(Box::new(db_pool_idle_connections.clone()))?;
+        registry.register(Box::new(auth_rejections.clone()))?;
+        registry.register(Box::new(analytics_dropped.clone()))?;
+        registry.register(Box::new(feedback_clicks.clone()))?;
+        registry.register(Box::new(documents_written.clone()))?;
+        registry.register(Box::new(change_feed_catch_ups.clone()))?;
+
+        Ok(Self {
+            registry,
+            search_latency,
+            search_hits,
+            search_zero_results,
+            search_cache_requests,
+            search_cache_entries,
+            index_duration,
+            index_documents,
+            index_segments,
+            index_age,
+            index_stale,
+            db_pool_connections,
+            db_pool_idle_connections,
+            auth_rejections,
+            analytics_dropped,
+            feedback_clicks,
+            documents_written,
+            change_feed_catch_ups,
+        })
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    pub fn observe_search(&self, entity: Entity, language: Language, elapsed: Duration, hits: usize) {
+        let labels = labels(entity, language);
+        self.search_latency.with_label_values(&[labels[0], labels[1], OK_OUTCOME]).observe(elapsed.as_secs_f64());
+        self.search_hits.with_label_values(&labels).observe(hits as f64);
+        if hits == 0 {
+            self.search_zero_results.with_label_values(&labels).inc();
+        }
+    }
+
+    /// Latency of a search request which failed, `client_error` telling whether the request was at fault.
+    pub fn observe_search_failure(&self, entity: Entity, language: Language, elapsed: Duration, client_error: bool) {
+        let [entity, language] = labels(entity, language);
+        let outcome = if client_error { CLIENT_ERROR_OUTCOME } else { SERVER_ERROR_OUTCOME };
+        self.search_latency.with_label_values(&[entity, language, outcome]).observe(elapsed.as_secs_f64());
+    }
+
+    pub fn observe_search_cache(&self, entity: Entity, language: Language, hit: bool) {
+        let [entity, language] = labels(entity, language);
+        let result = if hit { HIT_RESULT } else { MISS_RESULT };
+        self.search_cache_requests.with_label_values(&[entity, language, result]).inc();
+    }
+
+    pub fn set_search_cache_entries(&self, entries: u64) {
+        self.search_cache_entries.set(entries as i64);
+    }
+
+    pub fn observe_index_build(&self, entity: Entity, language: Language, elapsed: Duration, documents: usize) {
+        let labels = labels(entity, language);
+        self.index_duration.with_label_values(&labels).observe(elapsed.as_secs_f64());
+        self.index_documents.with_label_values(&labels).set(documents as i64);
+    }
+
+    pub fn set_index_segments(&self, entity: Entity, language: Language, segments: usize) {
+        self.index_segments.with_label_values(&labels(entity, language)).set(segments as i64);
+    }
+
+    pub fn set_index_age(&self, entity: Entity, language: Language, age: Option<Duration>, stale: bool) {
+        let labels = labels(entity, language);
+        // Never built indexes report -1, an age of 0 would read as freshly built
+        self.index_age.with_label_values(&labels).set(age.map(|age| age.as_secs() as i64).unwrap_or(-1));
+        self.index_stale.with_label_values(&labels).set(stale as i64);
+    }
+
+    pub fn set_db_pool(&self, connections: u32, idle_connections: usize) {
+        self.db_pool_connections.set(connections as i64);
+        self.db_pool_idle_connections.set(idle_connections as i64);
+    }
+
+    /// `client` is the name of the API key, or empty when the request carried no valid key.
+    pub fn observe_auth_rejection(&self, client: &str, code: &str) {
+        self.auth_rejections.with_label_values(&[client, code]).inc();
+    }
+
+    pub fn observe_analytics_dropped(&self) {
+        self.analytics_dropped.inc();
+    }
+
+    pub fn observe_click(&self, entity: Entity, language: Language) {
+        self.feedback_clicks.with_label_values(&labels(entity, language)).inc();
+    }
+
+    /// `operation` is `upsert` or `delete`.
+    pub fn observe_documents_written(&self, entity: Entity, language: Language, operation: &str, documents: usize) {
+        let [entity, language] = labels(entity, language);
+        self.documents_written.with_label_values(&[entity, language, operation]).inc_by(documents as u64);
+    }
+
+    pub fn observe_change_feed_catch_up(&self) {
+        self.change_feed_catch_ups.inc();
+    }
+
+    pub fn encode(&self) -> anyhow::Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+fn labels(entity: Entity, language: Language) -> [&'static str; 2] {
+    [entity.into(), language.into()]
+}
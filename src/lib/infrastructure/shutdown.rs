@@ -0,0 +1,64 @@
+use tokio::signal;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
+
+/// Cloneable handle used to request and observe the shutdown of the whole application.
+#[derive(Clone, Default)]
+pub struct ShutdownHandle {
+    token: CancellationToken,
+}
+
+impl ShutdownHandle {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn shutdown(&self) {
+        self.token.cancel();
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    pub async fn wait(&self) {
+        self.token.cancelled().await
+    }
+
+    pub fn wait_owned(self) -> WaitForCancellationFutureOwned {
+        self.token.cancelled_owned()
+    }
+
+    /// Triggers the shutdown as soon as SIGINT or SIGTERM is received.
+    pub fn listen_signals(&self) {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = interrupt() => log::info!("received SIGINT, shutting down..."),
+                _ = terminate() => log::info!("received SIGTERM, shutting down..."),
+                _ = handle.wait() => return,
+            }
+            handle.shutdown();
+        });
+    }
+}
+
+async fn interrupt() {
+    if signal::ctrl_c().await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+#[cfg(unix)]
+async fn terminate() {
+    match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            terminate.recv().await;
+        }
+        Err(_) => std::future::pending::<()>().await,
+    }
+}
+
+#[cfg(not(unix))]
+async fn terminate() {
+    std::future::pending::<()>().await
+}
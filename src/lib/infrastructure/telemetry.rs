@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer, Registry};
+
+use crate::config::logger_config::{LogFormat, LoggerConfig};
+
+pub const SERVICE_NAME: &str = "content-search-service";
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Installs the global subscriber, which also receives every `log` record. Later calls, e.g. from
+/// several commands in one process, keep the first subscriber.
+pub fn init(config: &LoggerConfig) -> anyhow::Result<()> {
+    if !config.enabled() {
+        return Ok(());
+    }
+
+    let level = LevelFilter::from_str(config.level().as_str()).unwrap_or(LevelFilter::INFO);
+    let mut layers: Vec<BoxedLayer> = vec![match config.format() {
+        LogFormat::Text => fmt::layer().boxed(),
+        LogFormat::Json => fmt::layer().json().with_current_span(true).with_span_list(true).boxed(),
+    }];
+    if let Some(endpoint) = config.otlp_endpoint() {
+        layers.push(otlp_layer(endpoint)?);
+    }
+
+    if tracing_subscriber::registry().with(layers.with_filter(level)).try_init().is_err() {
+        log::debug!("tracing subscriber already installed");
+    }
+    Ok(())
+}
+
+/// Flushes spans pending export.
+pub fn shutdown() {
+    #[cfg(feature = "otlp")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+#[cfg(feature = "otlp")]
+fn otlp_layer(endpoint: &str) -> anyhow::Result<BoxedLayer> {
+    use opentelemetry::KeyValue;
+    use opentelemetry::trace::TracerProvider;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{runtime, trace, Resource};
+
+    let provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().http().with_endpoint(endpoint))
+        .with_trace_config(trace::Config::default()
+            .with_resource(Resource::new([KeyValue::new("service.name", SERVICE_NAME)])))
+        .install_batch(runtime::Tokio)?;
+    let tracer = provider.tracer(SERVICE_NAME);
+    opentelemetry::global::set_tracer_provider(provider);
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer).boxed())
+}
+
+#[cfg(not(feature = "otlp"))]
+fn otlp_layer(endpoint: &str) -> anyhow::Result<BoxedLayer> {
+    Err(anyhow::anyhow!("cannot export traces to {endpoint}, built without the otlp feature"))
+}
@@ -5,3 +5,4 @@ pub mod services;
 pub mod repositories;
 pub mod entities;
 pub mod config;
+pub mod cli;
\ No newline at end of file
@@ -1,3 +1,16 @@
 pub mod entity;
 pub mod language;
-pub mod doc_details;
\ No newline at end of file
+pub mod doc_details;
+pub mod readiness;
+pub mod search_error;
+pub mod scored_doc;
+pub mod snapshot_manifest;
+pub mod source_document;
+pub mod auth_error;
+pub mod client_usage;
+pub mod search_event;
+pub mod query_report;
+pub mod click_aggregate;
+pub mod curation_rule;
+pub mod blocked_id;
+pub mod change_event;
\ No newline at end of file
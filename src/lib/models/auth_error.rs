@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::config::auth_config::Scope;
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("an API key is required, send it as a bearer token or in the X-Api-Key header")]
+    MissingKey,
+    #[error("the API key is not valid")]
+    InvalidKey,
+    #[error("client '{0}' lacks the '{}' scope", .1.name())]
+    Forbidden(String, Scope),
+    #[error("client '{0}' exceeded its rate limit")]
+    RateLimited(String, Duration),
+    #[error("client '{0}' exhausted its quota")]
+    QuotaExceeded(String, Duration),
+    #[error("the '{}' routes are refused while auth is disabled", .0.name())]
+    AuthDisabled(Scope),
+}
+
+impl AuthError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AuthError::MissingKey => { "MISSING_API_KEY" }
+            AuthError::InvalidKey => { "INVALID_API_KEY" }
+            AuthError::Forbidden(..) => { "FORBIDDEN" }
+            AuthError::RateLimited(..) => { "RATE_LIMITED" }
+            AuthError::QuotaExceeded(..) => { "QUOTA_EXCEEDED" }
+            AuthError::AuthDisabled(..) => { "AUTH_DISABLED" }
+        }
+    }
+
+    /// Name of the client the error concerns, `None` when the key is missing or unknown.
+    pub fn client(&self) -> Option<&str> {
+        match self {
+            AuthError::Forbidden(client, _) | AuthError::RateLimited(client, _) | AuthError::QuotaExceeded(client, _) => { Some(client) }
+            AuthError::MissingKey | AuthError::InvalidKey | AuthError::AuthDisabled(..) => { None }
+        }
+    }
+
+    /// Time the client should wait before retrying, for throttled requests.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            AuthError::RateLimited(_, retry_after) | AuthError::QuotaExceeded(_, retry_after) => { Some(*retry_after) }
+            _ => { None }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Document excluded from search, typically after a legal takedown.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BlockedId {
+    entity: String,
+    id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    blocked_at: DateTime<Utc>,
+}
+
+impl BlockedId {
+    pub fn new(entity: String, id: u64, reason: Option<String>, blocked_at: DateTime<Utc>) -> Self {
+        Self { entity, id, reason, blocked_at }
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn blocked_at(&self) -> DateTime<Utc> {
+        self.blocked_at
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Payload of a change feed notification, e.g. `{"type": "MOVIE", "id": 42, "language": "EN"}`.
+/// It only names the changed document, its current content is read back from the data source.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangeEvent {
+    #[serde(rename = "type")]
+    entity: String,
+    id: u64,
+    language: String,
+}
+
+impl ChangeEvent {
+    pub fn new(entity: String, id: u64, language: String) -> Self {
+        Self { entity, id, language }
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+}
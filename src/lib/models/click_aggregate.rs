@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Clicks on one result of a normalized query, as saved between restarts.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClickAggregate {
+    entity: String,
+    language: String,
+    query: String,
+    id: u64,
+    #[serde(flatten)]
+    count: DecayedCount,
+}
+
+/// Click count decaying exponentially, so recent behaviour outweighs older one. Only the weight at
+/// `updated_at` is kept, the current weight is derived from the elapsed time.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct DecayedCount {
+    weight: f64,
+    updated_at: DateTime<Utc>,
+}
+
+impl ClickAggregate {
+    pub fn new(entity: String, language: String, query: String, id: u64, count: DecayedCount) -> Self {
+        Self { entity, language, query, id, count }
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn count(&self) -> DecayedCount {
+        self.count
+    }
+}
+
+impl DecayedCount {
+    pub fn new(weight: f64, updated_at: DateTime<Utc>) -> Self {
+        Self { weight, updated_at }
+    }
+
+    /// Weight at `now`, halved every `half_life` since the last update.
+    pub fn weight_at(&self, now: DateTime<Utc>, half_life: Duration) -> f64 {
+        let elapsed = (now - self.updated_at).to_std().unwrap_or_default();
+        self.weight * 0.5_f64.powf(elapsed.as_secs_f64() / half_life.as_secs_f64())
+    }
+
+    /// Adds `amount` clicks at `now`, after decaying the previous ones.
+    pub fn add(&mut self, amount: f64, now: DateTime<Utc>, half_life: Duration) {
+        self.weight = self.weight_at(now, half_life) + amount;
+        self.updated_at = self.updated_at.max(now);
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
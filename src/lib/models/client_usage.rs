@@ -0,0 +1,32 @@
+use crate::config::auth_config::Scope;
+
+/// Remaining allowance of an API client, `None` when the client has no such limit.
+#[derive(Debug, Clone)]
+pub struct ClientUsage {
+    name: String,
+    scopes: Vec<Scope>,
+    tokens: Option<f64>,
+    quota_remaining: Option<u64>,
+}
+
+impl ClientUsage {
+    pub fn new(name: String, scopes: Vec<Scope>, tokens: Option<f64>, quota_remaining: Option<u64>) -> Self {
+        Self { name, scopes, tokens, quota_remaining }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn tokens(&self) -> Option<f64> {
+        self.tokens
+    }
+
+    pub fn quota_remaining(&self) -> Option<u64> {
+        self.quota_remaining
+    }
+}
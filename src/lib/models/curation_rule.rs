@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+/// Editorial rule applied to the results of a normalized query, for every language when `language` is unset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CurationRule {
+    id: String,
+    query: String,
+    entity: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(flatten)]
+    action: CurationAction,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum CurationAction {
+    /// Puts `ids` first, in the given order, even when the query does not match them.
+    Pin { ids: Vec<u64> },
+    /// Removes `ids` from the results, taking precedence over pins and boosts.
+    Hide { ids: Vec<u64> },
+    /// Moves `ids` up by `positions` when the query matches them.
+    Boost { ids: Vec<u64>, positions: usize },
+}
+
+impl CurationRule {
+    pub fn new(id: String, query: String, entity: String, language: Option<String>, action: CurationAction) -> Self {
+        Self { id, query, entity, language, action }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn action(&self) -> &CurationAction {
+        &self.action
+    }
+}
+
+impl CurationAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CurationAction::Pin { .. } => { "pin" }
+            CurationAction::Hide { .. } => { "hide" }
+            CurationAction::Boost { .. } => { "boost" }
+        }
+    }
+
+    pub fn ids(&self) -> &[u64] {
+        match self {
+            CurationAction::Pin { ids } | CurationAction::Hide { ids } | CurationAction::Boost { ids, .. } => { ids }
+        }
+    }
+
+    /// Applies the action to `results`, best result first.
+    pub fn apply(&self, results: &mut Vec<u64>) {
+        match self {
+            CurationAction::Pin { ids } => {
+                results.retain(|id| !ids.contains(id));
+                results.splice(0..0, ids.iter().copied());
+            }
+            CurationAction::Hide { ids } => {
+                results.retain(|id| !ids.contains(id));
+            }
+            CurationAction::Boost { ids, positions } => {
+                for id in ids {
+                    if let Some(index) = results.iter().position(|result| result == id) {
+                        let result = results.remove(index);
+                        results.insert(index.saturating_sub(*positions), result);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 
+#[derive(Clone)]
 pub struct DocDetails {
     id: u64,
     title: String,
@@ -1,6 +1,11 @@
 use crate::models::entity::Entity::{Game, Movie, Recipe, Tv};
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub const MOVIE_ENTITY: &str = "MOVIE";
+pub const TV_ENTITY: &str = "TV";
+pub const GAME_ENTITY: &str = "GAME";
+pub const RECIPE_ENTITY: &str = "RECIPE";
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Entity {
     Movie,
     Tv,
@@ -8,15 +13,32 @@ pub enum Entity {
     Recipe,
 }
 
+impl Entity {
+    pub fn all() -> Vec<Entity> {
+        vec![Movie, Tv, Game, Recipe]
+    }
+}
+
+impl From<Entity> for &str {
+    fn from(value: Entity) -> Self {
+        match value {
+            Movie => { MOVIE_ENTITY }
+            Tv => { TV_ENTITY }
+            Game => { GAME_ENTITY }
+            Recipe => { RECIPE_ENTITY }
+        }
+    }
+}
+
 impl TryFrom<&str> for Entity {
     type Error = ();
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
-            "MOVIE" => { Ok(Movie) }
-            "TV" => { Ok(Tv) }
-            "GAME" => { Ok(Game) }
-            "RECIPE" => { Ok(Recipe) }
+            MOVIE_ENTITY => { Ok(Movie) }
+            TV_ENTITY => { Ok(Tv) }
+            GAME_ENTITY => { Ok(Game) }
+            RECIPE_ENTITY => { Ok(Recipe) }
             _ => { Err(()) }
         }
     }
-}
\ No newline at end of file
+}
@@ -3,7 +3,7 @@ use crate::models::language::Language::{En, Es};
 pub const ES_LANGUAGE: &str = "ES";
 pub const EN_LANGUAGE: &str = "EN";
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Language {
     Es,
     En,
@@ -13,6 +13,13 @@ impl Language {
     pub fn all() -> Vec<Language> {
         vec![Es, En]
     }
+
+    /// Language of a tag such as `EN`, `es` or `es-MX`, matched on its primary subtag ignoring case.
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        let primary = tag.trim().split(['-', '_']).next()?;
+        Language::all().into_iter()
+            .find(|language| <&str>::from(*language).eq_ignore_ascii_case(primary))
+    }
 }
 
 impl From<Language> for &str {
@@ -0,0 +1,50 @@
+/// Number of times a normalized query was searched for a content type and language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryCount {
+    query: String,
+    entity: String,
+    language: String,
+    count: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QueryReport {
+    top_queries: Vec<QueryCount>,
+    top_zero_result_queries: Vec<QueryCount>,
+}
+
+impl QueryCount {
+    pub fn new(query: String, entity: String, language: String, count: u64) -> Self {
+        Self { query, entity, language, count }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl QueryReport {
+    pub fn new(top_queries: Vec<QueryCount>, top_zero_result_queries: Vec<QueryCount>) -> Self {
+        Self { top_queries, top_zero_result_queries }
+    }
+
+    pub fn top_queries(&self) -> &[QueryCount] {
+        &self.top_queries
+    }
+
+    pub fn top_zero_result_queries(&self) -> &[QueryCount] {
+        &self.top_zero_result_queries
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::SystemTime;
+
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+
+pub struct Readiness {
+    database: Option<bool>,
+    database_required: bool,
+    indexes: Vec<IndexReadiness>,
+}
+
+pub struct IndexReadiness {
+    entity: Entity,
+    language: Language,
+    last_build: Option<SystemTime>,
+    stale: bool,
+}
+
+impl Readiness {
+    pub fn new(database: Option<bool>, database_required: bool, indexes: Vec<IndexReadiness>) -> Self {
+        Self { database, database_required, indexes }
+    }
+
+    /// Database connectivity, `None` when the database is not used.
+    pub fn database(&self) -> Option<bool> {
+        self.database
+    }
+
+    pub fn indexes(&self) -> &[IndexReadiness] {
+        &self.indexes[..]
+    }
+
+    pub fn is_ready(&self) -> bool {
+        let database_ready = !self.database_required || self.database.unwrap_or(true);
+        database_ready && self.indexes.iter().all(IndexReadiness::is_ready)
+    }
+
+    /// Ready but serving stale indexes or unable to refresh them from the database.
+    pub fn is_degraded(&self) -> bool {
+        self.is_ready() && (self.database == Some(false) || self.indexes.iter().any(IndexReadiness::stale))
+    }
+}
+
+impl IndexReadiness {
+    pub fn new(entity: Entity, language: Language, last_build: Option<SystemTime>, stale: bool) -> Self {
+        Self { entity, language, last_build, stale }
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn last_build(&self) -> Option<SystemTime> {
+        self.last_build
+    }
+
+    /// Index loaded from disk that was not rebuilt from the database yet.
+    pub fn stale(&self) -> bool {
+        self.stale
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.last_build.is_some()
+    }
+}
@@ -0,0 +1,23 @@
+pub struct ScoredDoc {
+    id: u64,
+    title: String,
+    score: f32,
+}
+
+impl ScoredDoc {
+    pub fn new(id: u64, title: String, score: f32) -> Self {
+        Self { id, title, score }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn score(&self) -> f32 {
+        self.score
+    }
+}
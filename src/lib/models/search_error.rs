@@ -0,0 +1,89 @@
+use thiserror::Error;
+
+pub const KEYWORD_FIELD: &str = "keyword";
+pub const TYPE_FIELD: &str = "type";
+pub const LANGUAGE_FIELD: &str = "Language";
+pub const LIMIT_FIELD: &str = "limit";
+pub const FILTERS_FIELD: &str = "filters";
+pub const ID_FIELD: &str = "id";
+
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("request payload is invalid: {0}")]
+    InvalidPayload(String),
+    #[error("unknown content type '{0}'")]
+    UnknownEntity(String),
+    #[error("unsupported language '{0}'")]
+    InvalidLanguage(String),
+    #[error("search keywords are empty")]
+    EmptyQuery,
+    #[error("search keywords are not a valid pattern: {0}")]
+    InvalidQuery(String),
+    #[error("limit must be between 1 and {1}, got {0}")]
+    InvalidLimit(usize, usize),
+    #[error("filter '{0}' is not supported, titles are the only indexed field")]
+    UnsupportedFilter(String),
+    #[error("batch holds {0} searches, at most {1} are allowed")]
+    BatchTooLarge(usize, usize),
+    #[error("curation rule is invalid: {0}")]
+    InvalidRule(String),
+    #[error("the blocklist is not enabled")]
+    BlocklistDisabled,
+    #[error("curation rules are not enabled")]
+    CurationDisabled,
+    #[error("click feedback is not enabled")]
+    FeedbackDisabled,
+    #[error("search analytics are not enabled")]
+    AnalyticsDisabled,
+    #[error("no indexed document has id {0}")]
+    UnknownDocument(u64),
+    #[error("no searcher registered for content type '{0}'")]
+    SearcherNotFound(String),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl SearchError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SearchError::InvalidPayload(_) => { "INVALID_PAYLOAD" }
+            SearchError::UnknownEntity(_) => { "UNKNOWN_TYPE" }
+            SearchError::InvalidLanguage(_) => { "INVALID_LANGUAGE" }
+            SearchError::EmptyQuery => { "EMPTY_QUERY" }
+            SearchError::InvalidQuery(_) => { "INVALID_QUERY" }
+            SearchError::InvalidLimit(..) => { "INVALID_LIMIT" }
+            SearchError::UnsupportedFilter(_) => { "UNSUPPORTED_FILTER" }
+            SearchError::BatchTooLarge(..) => { "BATCH_TOO_LARGE" }
+            SearchError::InvalidRule(_) => { "INVALID_RULE" }
+            SearchError::BlocklistDisabled => { "BLOCKLIST_DISABLED" }
+            SearchError::CurationDisabled => { "CURATION_DISABLED" }
+            SearchError::FeedbackDisabled => { "FEEDBACK_DISABLED" }
+            SearchError::AnalyticsDisabled => { "ANALYTICS_DISABLED" }
+            SearchError::UnknownDocument(_) => { "UNKNOWN_DOCUMENT" }
+            SearchError::SearcherNotFound(_) => { "SEARCHER_NOT_FOUND" }
+            SearchError::Internal(_) => { "INTERNAL_ERROR" }
+        }
+    }
+
+    /// Request field the error originates from, if any.
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            SearchError::UnknownEntity(_) => { Some(TYPE_FIELD) }
+            SearchError::InvalidLanguage(_) => { Some(LANGUAGE_FIELD) }
+            SearchError::EmptyQuery | SearchError::InvalidQuery(_) => { Some(KEYWORD_FIELD) }
+            SearchError::InvalidLimit(..) => { Some(LIMIT_FIELD) }
+            SearchError::UnsupportedFilter(_) => { Some(FILTERS_FIELD) }
+            SearchError::UnknownDocument(_) => { Some(ID_FIELD) }
+            _ => { None }
+        }
+    }
+
+    /// Whether the error is about a feature turned off in the configuration rather than about the request.
+    pub fn is_disabled(&self) -> bool {
+        matches!(self, SearchError::BlocklistDisabled | SearchError::CurationDisabled | SearchError::FeedbackDisabled | SearchError::AnalyticsDisabled)
+    }
+
+    pub fn is_client_error(&self) -> bool {
+        !matches!(self, SearchError::SearcherNotFound(_) | SearchError::Internal(_))
+    }
+}
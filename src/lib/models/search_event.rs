@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+
+/// A served search, with its keywords normalized the way they are matched.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchEvent {
+    timestamp: DateTime<Utc>,
+    query: String,
+    entity: String,
+    language: String,
+    hits: usize,
+    top_ids: Vec<u64>,
+    latency_ms: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client: Option<String>,
+}
+
+impl SearchEvent {
+    pub fn new(entity: Entity, language: Language, keywords: &str, ids: &[u64], top_ids: usize, latency: Duration, client: Option<String>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            query: Self::normalize(keywords),
+            entity: <&str>::from(entity).to_string(),
+            language: <&str>::from(language).to_string(),
+            hits: ids.len(),
+            top_ids: ids.iter().take(top_ids).copied().collect(),
+            latency_ms: latency.as_secs_f64() * 1000.0,
+            client,
+        }
+    }
+
+    /// Lowercase keywords separated by single spaces.
+    pub fn normalize(keywords: &str) -> String {
+        keywords.split_whitespace().collect::<Vec<_>>().join(" ").to_ascii_lowercase()
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn top_ids(&self) -> &[u64] {
+        &self.top_ids
+    }
+
+    pub fn latency_ms(&self) -> f64 {
+        self.latency_ms
+    }
+
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+}
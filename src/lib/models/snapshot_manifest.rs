@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Describes the content of an index snapshot archive, stored as its first entry.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SnapshotManifest {
+    format_version: u32,
+    schema_version: u32,
+    #[serde(rename = "type")]
+    entity: String,
+    language: String,
+    created_at: u64,
+    documents: u64,
+    sha256: String,
+    files: Vec<SnapshotFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SnapshotFile {
+    name: String,
+    size: u64,
+    sha256: String,
+}
+
+impl SnapshotManifest {
+    pub fn new(format_version: u32, schema_version: u32, entity: String, language: String, created_at: u64, documents: u64, files: Vec<SnapshotFile>) -> Self {
+        let sha256 = files_sha256(&files);
+        Self { format_version, schema_version, entity, language, created_at, documents, sha256, files }
+    }
+
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn documents(&self) -> u64 {
+        self.documents
+    }
+
+    /// Digest of the name, size and checksum of every file as exported, so they can't be altered one by one.
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    /// Whether the files still match the digest computed at export.
+    pub fn is_consistent(&self) -> bool {
+        files_sha256(&self.files) == self.sha256
+    }
+
+    pub fn files(&self) -> &[SnapshotFile] {
+        &self.files[..]
+    }
+}
+
+fn files_sha256(files: &[SnapshotFile]) -> String {
+    let mut digest = Sha256::new();
+    for file in files {
+        digest.update(format!("{} {} {}\n", file.name, file.size, file.sha256));
+    }
+    hex::encode(digest.finalize())
+}
+
+impl SnapshotFile {
+    pub fn new(name: String, size: u64, sha256: String) -> Self {
+        Self { name, size, sha256 }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+}
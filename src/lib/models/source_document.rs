@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Document of a file or in-memory data source, the same shape is used by JSONL lines, CSV rows and config fixtures.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct SourceDocument {
+    id: u64,
+    language: String,
+    title: String,
+}
+
+impl SourceDocument {
+    pub fn new(id: u64, language: String, title: String) -> Self {
+        Self { id, language, title }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+}
@@ -1,4 +1,7 @@
 pub mod movie_repository_impl;
 pub mod tv_repository_impl;
 pub mod recipe_repository_impl;
-pub mod game_repository_impl;
\ No newline at end of file
+pub mod game_repository_impl;
+pub mod search_event_repository_impl;
+pub mod curation_rule_repository_impl;
+pub mod blocked_id_repository_impl;
\ No newline at end of file
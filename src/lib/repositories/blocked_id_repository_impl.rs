@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::{Pool, Postgres, query, QueryBuilder, Row};
+
+use crate::infrastructure::di_container::{DB_POOL_DEP, DIContainer};
+use crate::models::blocked_id::BlockedId;
+
+const CREATE_SCHEMA: &str = "CREATE SCHEMA IF NOT EXISTS blocklist";
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS blocklist.blocked_ids (
+    entity TEXT NOT NULL,
+    id BIGINT NOT NULL,
+    reason TEXT,
+    blocked_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (entity, id)
+)";
+
+pub struct BlockedIdRepositoryImpl {
+    db_pool: Arc<Pool<Postgres>>,
+}
+
+impl BlockedIdRepositoryImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        Ok(Self { db_pool: di_container.get(DB_POOL_DEP)? })
+    }
+}
+
+#[async_trait]
+pub trait BlockedIdRepository: Send + Sync {
+    async fn create_table(&self) -> anyhow::Result<()>;
+
+    async fn find_all(&self) -> anyhow::Result<Vec<BlockedId>>;
+
+    /// Inserts the ids not blocked yet, keeping the reason and time of the ones already blocked.
+    async fn insert(&self, blocked: &[BlockedId]) -> anyhow::Result<()>;
+
+    /// Whether the id was blocked.
+    async fn delete(&self, entity: &str, id: u64) -> anyhow::Result<bool>;
+}
+
+#[async_trait]
+impl BlockedIdRepository for BlockedIdRepositoryImpl {
+    async fn create_table(&self) -> anyhow::Result<()> {
+        for statement in [CREATE_SCHEMA, CREATE_TABLE] {
+            query(statement).execute(&*self.db_pool).await?;
+        }
+        Ok(())
+    }
+
+    async fn find_all(&self) -> anyhow::Result<Vec<BlockedId>> {
+        let rows = query("SELECT entity, id, reason, blocked_at FROM blocklist.blocked_ids ORDER BY entity, id")
+            .fetch_all(&*self.db_pool)
+            .await?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let id: i64 = row.try_get("id")?;
+            result.push(BlockedId::new(row.try_get("entity")?, id as u64, row.try_get("reason")?, row.try_get("blocked_at")?));
+        }
+
+        Ok(result)
+    }
+
+    async fn insert(&self, blocked: &[BlockedId]) -> anyhow::Result<()> {
+        if blocked.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder = QueryBuilder::<Postgres>::new("INSERT INTO blocklist.blocked_ids (entity, id, reason, blocked_at) ");
+        builder.push_values(blocked, |mut row, blocked| {
+            row.push_bind(blocked.entity())
+                .push_bind(blocked.id() as i64)
+                .push_bind(blocked.reason())
+                .push_bind(blocked.blocked_at());
+        });
+        builder.push(" ON CONFLICT (entity, id) DO NOTHING");
+        builder.build().execute(&*self.db_pool).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, entity: &str, id: u64) -> anyhow::Result<bool> {
+        let result = query("DELETE FROM blocklist.blocked_ids WHERE entity = $1 AND id = $2")
+            .bind(entity)
+            .bind(id as i64)
+            .execute(&*self.db_pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
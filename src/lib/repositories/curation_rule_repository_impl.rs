@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use sqlx::{Pool, Postgres, query, Row};
+
+use crate::infrastructure::di_container::{DB_POOL_DEP, DIContainer};
+use crate::models::curation_rule::{CurationAction, CurationRule};
+
+const CREATE_SCHEMA: &str = "CREATE SCHEMA IF NOT EXISTS curation";
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS curation.rules (
+    id TEXT PRIMARY KEY,
+    query TEXT NOT NULL,
+    entity TEXT NOT NULL,
+    language TEXT,
+    action TEXT NOT NULL,
+    ids BIGINT[] NOT NULL,
+    positions INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+)";
+
+pub struct CurationRuleRepositoryImpl {
+    db_pool: Arc<Pool<Postgres>>,
+}
+
+impl CurationRuleRepositoryImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        Ok(Self { db_pool: di_container.get(DB_POOL_DEP)? })
+    }
+}
+
+#[async_trait]
+pub trait CurationRuleRepository: Send + Sync {
+    async fn create_table(&self) -> anyhow::Result<()>;
+
+    async fn find_all(&self) -> anyhow::Result<Vec<CurationRule>>;
+
+    async fn upsert(&self, rule: &CurationRule) -> anyhow::Result<()>;
+
+    /// Whether a rule with `id` existed.
+    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
+}
+
+#[async_trait]
+impl CurationRuleRepository for CurationRuleRepositoryImpl {
+    async fn create_table(&self) -> anyhow::Result<()> {
+        for statement in [CREATE_SCHEMA, CREATE_TABLE] {
+            query(statement).execute(&*self.db_pool).await?;
+        }
+        Ok(())
+    }
+
+    async fn find_all(&self) -> anyhow::Result<Vec<CurationRule>> {
+        let rows = query("SELECT id, query, entity, language, action, ids, positions FROM curation.rules ORDER BY id")
+            .fetch_all(&*self.db_pool)
+            .await?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let ids = row.try_get::<Vec<i64>, _>("ids")?.into_iter().map(|id| id as u64).collect();
+            let action: String = row.try_get("action")?;
+            let action = match action.as_str() {
+                "pin" => CurationAction::Pin { ids },
+                "hide" => CurationAction::Hide { ids },
+                "boost" => CurationAction::Boost { ids, positions: row.try_get::<i32, _>("positions")?.max(0) as usize },
+                other => return Err(anyhow!("unknown curation action '{other}'")),
+            };
+            result.push(CurationRule::new(row.try_get("id")?, row.try_get("query")?, row.try_get("entity")?, row.try_get("language")?, action));
+        }
+
+        Ok(result)
+    }
+
+    async fn upsert(&self, rule: &CurationRule) -> anyhow::Result<()> {
+        let positions = match rule.action() {
+            CurationAction::Boost { positions, .. } => *positions as i32,
+            _ => 0,
+        };
+        query("INSERT INTO curation.rules (id, query, entity, language, action, ids, positions) VALUES ($1, $2, $3, $4, $5, $6, $7) \
+               ON CONFLICT (id) DO UPDATE SET query = EXCLUDED.query, entity = EXCLUDED.entity, language = EXCLUDED.language, \
+               action = EXCLUDED.action, ids = EXCLUDED.ids, positions = EXCLUDED.positions, updated_at = now()")
+            .bind(rule.id())
+            .bind(rule.query())
+            .bind(rule.entity())
+            .bind(rule.language())
+            .bind(rule.action().name())
+            .bind(rule.action().ids().iter().map(|id| *id as i64).collect::<Vec<i64>>())
+            .bind(positions)
+            .execute(&*self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let result = query("DELETE FROM curation.rules WHERE id = $1")
+            .bind(id)
+            .execute(&*self.db_pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -11,20 +11,23 @@ pub struct GameRepositoryImpl {
 
 impl GameRepositoryImpl {
     pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
-        Ok(Self { db_pool: di_container.get::<Pool<Postgres>>(DB_POOL_DEP) })
+        Ok(Self { db_pool: di_container.get(DB_POOL_DEP)? })
     }
 }
 
 #[async_trait]
-pub trait GameRepository {
+pub trait GameRepository: Send + Sync {
     async fn find_games_by_lang_and_limit_offset(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<Game>>;
+
+    async fn find_games_by_lang_and_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<Game>>;
 }
 
 
 #[async_trait]
 impl GameRepository for GameRepositoryImpl {
     async fn find_games_by_lang_and_limit_offset(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<Game>> {
-        let rows = query("SELECT game_id, name FROM game.game LIMIT $2 OFFSET $3")
+        // Names are not translated, a game is listed in the languages it has details in
+        let rows = query("SELECT game_id, name FROM game.game g WHERE EXISTS (SELECT 1 FROM game.game_details d WHERE d.game_id = g.game_id AND d.language = $1) ORDER BY game_id, name LIMIT $2 OFFSET $3")
             .bind(lang)
             .bind(limit as i64)
             .bind(offset as i64)
@@ -40,4 +43,21 @@ impl GameRepository for GameRepositoryImpl {
 
         Ok(result)
     }
+
+    async fn find_games_by_lang_and_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<Game>> {
+        let rows = query("SELECT game_id, name FROM game.game g WHERE EXISTS (SELECT 1 FROM game.game_details d WHERE d.game_id = g.game_id AND d.language = $1) AND game_id = ANY($2) ORDER BY game_id, name")
+            .bind(lang)
+            .bind(ids.iter().map(|id| *id as i64).collect::<Vec<_>>())
+            .fetch_all(&*self.db_pool)
+            .await?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let id: u64 = row.try_get::<i64, &str>("game_id")? as u64;
+            let name: String = row.try_get("name")?;
+            result.push(Game::new(id, name));
+        }
+
+        Ok(result)
+    }
 }
\ No newline at end of file
@@ -12,20 +12,22 @@ pub struct MovieRepositoryImpl {
 
 impl MovieRepositoryImpl {
     pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
-        Ok(Self { db_pool: di_container.get::<Pool<Postgres>>(DB_POOL_DEP) })
+        Ok(Self { db_pool: di_container.get(DB_POOL_DEP)? })
     }
 }
 
 #[async_trait]
-pub trait MovieRepository {
+pub trait MovieRepository: Send + Sync {
     async fn find_movies_by_lang_and_limit_offset(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<Movie>>;
+
+    async fn find_movies_by_lang_and_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<Movie>>;
 }
 
 
 #[async_trait]
 impl MovieRepository for MovieRepositoryImpl {
     async fn find_movies_by_lang_and_limit_offset(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<Movie>> {
-        let rows = query("SELECT movie_id, title FROM movie.movie_details WHERE language = $1 LIMIT $2 OFFSET $3")
+        let rows = query("SELECT movie_id, title FROM movie.movie_details WHERE language = $1 ORDER BY movie_id, title LIMIT $2 OFFSET $3")
             .bind(lang)
             .bind(limit as i64)
             .bind(offset as i64)
@@ -41,4 +43,21 @@ impl MovieRepository for MovieRepositoryImpl {
 
         Ok(result)
     }
+
+    async fn find_movies_by_lang_and_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<Movie>> {
+        let rows = query("SELECT movie_id, title FROM movie.movie_details WHERE language = $1 AND movie_id = ANY($2) ORDER BY movie_id, title")
+            .bind(lang)
+            .bind(ids.iter().map(|id| *id as i64).collect::<Vec<_>>())
+            .fetch_all(&*self.db_pool)
+            .await?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let id: u64 = row.try_get::<i64, &str>("movie_id")? as u64;
+            let name: String = row.try_get("title")?;
+            result.push(Movie::new(id, name));
+        }
+
+        Ok(result)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, query, QueryBuilder, Row};
+
+use crate::infrastructure::di_container::{DB_POOL_DEP, DIContainer};
+use crate::models::query_report::QueryCount;
+use crate::models::search_event::SearchEvent;
+
+const CREATE_SCHEMA: &str = "CREATE SCHEMA IF NOT EXISTS analytics";
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS analytics.search_events (
+    occurred_at TIMESTAMPTZ NOT NULL,
+    query TEXT NOT NULL,
+    entity TEXT NOT NULL,
+    language TEXT NOT NULL,
+    hits INTEGER NOT NULL,
+    top_ids BIGINT[] NOT NULL,
+    latency_ms DOUBLE PRECISION NOT NULL,
+    client TEXT
+)";
+const CREATE_INDEX: &str = "CREATE INDEX IF NOT EXISTS search_events_occurred_at_idx ON analytics.search_events (occurred_at)";
+
+pub struct SearchEventRepositoryImpl {
+    db_pool: Arc<Pool<Postgres>>,
+}
+
+impl SearchEventRepositoryImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        Ok(Self { db_pool: di_container.get(DB_POOL_DEP)? })
+    }
+}
+
+#[async_trait]
+pub trait SearchEventRepository: Send + Sync {
+    async fn create_table(&self) -> anyhow::Result<()>;
+
+    async fn insert_events(&self, events: &[SearchEvent]) -> anyhow::Result<()>;
+
+    async fn find_top_queries(&self, since: DateTime<Utc>, zero_hits_only: bool, limit: usize) -> anyhow::Result<Vec<QueryCount>>;
+}
+
+#[async_trait]
+impl SearchEventRepository for SearchEventRepositoryImpl {
+    async fn create_table(&self) -> anyhow::Result<()> {
+        for statement in [CREATE_SCHEMA, CREATE_TABLE, CREATE_INDEX] {
+            query(statement).execute(&*self.db_pool).await?;
+        }
+        Ok(())
+    }
+
+    async fn insert_events(&self, events: &[SearchEvent]) -> anyhow::Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "INSERT INTO analytics.search_events (occurred_at, query, entity, language, hits, top_ids, latency_ms, client) ");
+        builder.push_values(events, |mut row, event| {
+            row.push_bind(event.timestamp())
+                .push_bind(event.query())
+                .push_bind(event.entity())
+                .push_bind(event.language())
+                .push_bind(event.hits() as i32)
+                .push_bind(event.top_ids().iter().map(|id| *id as i64).collect::<Vec<i64>>())
+                .push_bind(event.latency_ms())
+                .push_bind(event.client());
+        });
+        builder.build().execute(&*self.db_pool).await?;
+        Ok(())
+    }
+
+    async fn find_top_queries(&self, since: DateTime<Utc>, zero_hits_only: bool, limit: usize) -> anyhow::Result<Vec<QueryCount>> {
+        let rows = query("SELECT query, entity, language, COUNT(*) AS searches FROM analytics.search_events \
+                          WHERE occurred_at >= $1 AND (NOT $2 OR hits = 0) \
+                          GROUP BY query, entity, language ORDER BY searches DESC, query LIMIT $3")
+            .bind(since)
+            .bind(zero_hits_only)
+            .bind(limit as i64)
+            .fetch_all(&*self.db_pool)
+            .await?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let count: i64 = row.try_get("searches")?;
+            result.push(QueryCount::new(row.try_get("query")?, row.try_get("entity")?, row.try_get("language")?, count as u64));
+        }
+
+        Ok(result)
+    }
+}
@@ -1,6 +1,21 @@
 pub mod search_service_impl;
+pub mod health_service_impl;
+pub mod metrics_service_impl;
 pub mod index_processor;
 pub mod impls;
-mod doc_details_retriever;
+pub mod doc_details_retriever;
 pub mod index_task;
-pub mod indexer_runner;
\ No newline at end of file
+pub mod indexer_runner;
+pub mod index_schedule;
+pub mod index_snapshot;
+pub mod rate_limiter;
+pub mod api_key_service_impl;
+pub mod search_event_sink;
+pub mod analytics_service_impl;
+pub mod feedback_service_impl;
+pub mod curation_rule_store;
+pub mod curation_service_impl;
+pub mod blocklist_store;
+pub mod blocklist_service_impl;
+pub mod document_service_impl;
+pub mod change_feed_service_impl;
\ No newline at end of file
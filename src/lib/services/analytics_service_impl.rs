@@ -0,0 +1,149 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::async_trait;
+use chrono::Utc;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::JoinHandle;
+use tokio::time;
+
+use crate::infrastructure::di_container::{CONFIG_DEP, DIContainer, METRICS_DEP, SEARCH_EVENT_SINK_DEP};
+use crate::infrastructure::metrics::Metrics;
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::models::query_report::QueryReport;
+use crate::models::search_event::SearchEvent;
+use crate::services::search_event_sink::SearchEventSink;
+
+const WRITE_BATCH_SIZE: usize = 500;
+
+/// Buffers search events in a bounded channel drained by a background writer, so recording never
+/// blocks a search. Events are dropped, and counted, while the buffer is full.
+pub struct AnalyticsServiceImpl {
+    sink: Option<Arc<dyn SearchEventSink>>,
+    sender: Option<Sender<SearchEvent>>,
+    receiver: Mutex<Option<Receiver<SearchEvent>>>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+    stopping: ShutdownHandle,
+    top_ids: usize,
+    metrics: Arc<Metrics>,
+}
+
+impl AnalyticsServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let analytics = config.analytics();
+        let (sink, sender, receiver) = if analytics.enabled() {
+            let (sender, receiver) = mpsc::channel(analytics.buffer_size());
+            (Some(di_container.get(SEARCH_EVENT_SINK_DEP)?), Some(sender), Some(receiver))
+        } else {
+            (None, None, None)
+        };
+
+        Ok(Self {
+            sink,
+            sender,
+            receiver: Mutex::new(receiver),
+            writer: Mutex::new(None),
+            stopping: ShutdownHandle::new(),
+            top_ids: analytics.top_ids(),
+            metrics: di_container.get(METRICS_DEP)?,
+        })
+    }
+
+    /// Leading result ids to keep per event.
+    pub fn top_ids(&self) -> usize {
+        self.top_ids
+    }
+
+    /// Spawns the background writer, a no-op when analytics are disabled or the writer already runs.
+    pub fn start(&self) {
+        let receiver = self.receiver.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        let (Some(sink), Some(receiver)) = (self.sink.clone(), receiver) else {
+            return;
+        };
+
+        let writer = tokio::spawn(Self::write_events(sink, receiver, self.stopping.clone()));
+        *self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(writer);
+    }
+
+    /// Writes the buffered events and stops the writer, waiting at most `timeout`.
+    pub async fn stop(&self, timeout: Duration) {
+        self.stopping.shutdown();
+        let writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        if let Some(writer) = writer {
+            log::info!("writing buffered search events...");
+            if time::timeout(timeout, writer).await.is_err() {
+                log::warn!("search events writer exceeded {}s, dropping buffered events", timeout.as_secs());
+            }
+        }
+    }
+
+    async fn write_events(sink: Arc<dyn SearchEventSink>, mut receiver: Receiver<SearchEvent>, stopping: ShutdownHandle) {
+        let mut batch = Vec::with_capacity(WRITE_BATCH_SIZE);
+        loop {
+            tokio::select! {
+                received = receiver.recv_many(&mut batch, WRITE_BATCH_SIZE) => {
+                    if received == 0 {
+                        break;
+                    }
+                    Self::write_batch(sink.as_ref(), &mut batch).await;
+                }
+                _ = stopping.wait() => break,
+            }
+        }
+
+        receiver.close();
+        while receiver.recv_many(&mut batch, WRITE_BATCH_SIZE).await > 0 {
+            Self::write_batch(sink.as_ref(), &mut batch).await;
+        }
+    }
+
+    async fn write_batch(sink: &dyn SearchEventSink, batch: &mut Vec<SearchEvent>) {
+        if let Err(err) = sink.write(batch).await {
+            log::warn!("failed writing {} search events: {err:?}", batch.len());
+        }
+        batch.clear();
+    }
+}
+
+#[async_trait]
+pub trait AnalyticsService {
+    fn enabled(&self) -> bool;
+
+    fn record(&self, event: SearchEvent);
+
+    /// Most searched and most searched zero-result queries of the last `period`.
+    async fn report(&self, period: Duration, limit: usize) -> anyhow::Result<QueryReport>;
+}
+
+#[async_trait]
+impl AnalyticsService for AnalyticsServiceImpl {
+    fn enabled(&self) -> bool {
+        self.sender.is_some()
+    }
+
+    fn record(&self, event: SearchEvent) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if let Err(err) = sender.try_send(event) {
+            if matches!(err, TrySendError::Full(_)) {
+                log::debug!("search events buffer full, dropping event");
+            }
+            self.metrics.observe_analytics_dropped();
+        }
+    }
+
+    async fn report(&self, period: Duration, limit: usize) -> anyhow::Result<QueryReport> {
+        let Some(sink) = &self.sink else {
+            return Ok(QueryReport::default());
+        };
+
+        let since = Utc::now() - period;
+        Ok(QueryReport::new(
+            sink.top_queries(since, false, limit).await?,
+            sink.top_queries(since, true, limit).await?,
+        ))
+    }
+}
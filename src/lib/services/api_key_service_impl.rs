@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use crate::config::auth_config::{ApiKeyConfig, Scope};
+use crate::infrastructure::di_container::{CONFIG_DEP, DIContainer, METRICS_DEP};
+use crate::infrastructure::metrics::Metrics;
+use crate::models::auth_error::AuthError;
+use crate::models::client_usage::ClientUsage;
+use crate::services::rate_limiter::{QuotaWindow, TokenBucket};
+
+pub struct ApiKeyServiceImpl {
+    enabled: bool,
+    clients: HashMap<String, ApiClient>,
+    metrics: Arc<Metrics>,
+}
+
+struct ApiClient {
+    name: String,
+    scopes: Vec<Scope>,
+    limits: Mutex<ClientLimits>,
+}
+
+/// Limits live in memory, so a restart refills every bucket and quota.
+struct ClientLimits {
+    bucket: Option<TokenBucket>,
+    quota: Option<QuotaWindow>,
+}
+
+impl ApiKeyServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let now = Instant::now();
+        let clients = config.auth().keys().iter()
+            .map(|key| (key.key_sha256().to_ascii_lowercase(), ApiClient::new(key, now)))
+            .collect();
+
+        Ok(Self {
+            enabled: config.auth().enabled(),
+            clients,
+            metrics: di_container.get(METRICS_DEP)?,
+        })
+    }
+
+    /// Hex encoded SHA-256 of `key`, the form keys are configured in.
+    pub fn hash_key(key: &str) -> String {
+        hex::encode(Sha256::digest(key.as_bytes()))
+    }
+
+    fn check(&self, key: Option<&str>, scope: Scope, now: Instant) -> Result<String, AuthError> {
+        let key = key.ok_or(AuthError::MissingKey)?;
+        let client = self.clients.get(&Self::hash_key(key)).ok_or(AuthError::InvalidKey)?;
+        if !client.allows(scope) {
+            return Err(AuthError::Forbidden(client.name.clone(), scope));
+        }
+        client.acquire(now)?;
+        Ok(client.name.clone())
+    }
+}
+
+impl ApiClient {
+    fn new(config: &ApiKeyConfig, now: Instant) -> Self {
+        let limits = ClientLimits {
+            bucket: config.rate_limit().map(|rate_limit| TokenBucket::new(rate_limit.burst(), rate_limit.per_second(), now)),
+            quota: config.quota().map(|quota| QuotaWindow::new(quota.requests(), Duration::from_secs(quota.period()))),
+        };
+        Self { name: config.name().to_string(), scopes: config.scopes().to_vec(), limits: Mutex::new(limits) }
+    }
+
+    fn allows(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope) || self.scopes.contains(&Scope::Admin)
+    }
+
+    /// Counts the request against both limits only when both allow it, so throttled requests cost nothing.
+    fn acquire(&self, now: Instant) -> Result<(), AuthError> {
+        let mut limits = self.limits.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(retry_after) = limits.quota.as_mut().and_then(|quota| quota.retry_after(now)) {
+            return Err(AuthError::QuotaExceeded(self.name.clone(), retry_after));
+        }
+        if let Some(retry_after) = limits.bucket.as_mut().and_then(|bucket| bucket.retry_after(now)) {
+            return Err(AuthError::RateLimited(self.name.clone(), retry_after));
+        }
+
+        if let Some(quota) = limits.quota.as_mut() {
+            quota.take();
+        }
+        if let Some(bucket) = limits.bucket.as_mut() {
+            bucket.take();
+        }
+        Ok(())
+    }
+
+    fn usage(&self) -> ClientUsage {
+        let limits = self.limits.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        ClientUsage::new(
+            self.name.clone(),
+            self.scopes.clone(),
+            limits.bucket.as_ref().map(TokenBucket::tokens),
+            limits.quota.as_ref().map(QuotaWindow::remaining),
+        )
+    }
+}
+
+pub trait ApiKeyService {
+    /// Whether requests must carry an API key, when disabled searches are allowed and every other route refused.
+    fn enabled(&self) -> bool;
+
+    /// Authenticates `key` for `scope` and counts the request against the limits of its client,
+    /// answering the client name.
+    fn authorize(&self, key: Option<&str>, scope: Scope) -> Result<String, AuthError>;
+
+    fn usage(&self) -> Vec<ClientUsage>;
+}
+
+impl ApiKeyService for ApiKeyServiceImpl {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn authorize(&self, key: Option<&str>, scope: Scope) -> Result<String, AuthError> {
+        self.check(key, scope, Instant::now()).inspect_err(|err| {
+            log::debug!("rejected request: {err}");
+            self.metrics.observe_auth_rejection(err.client().unwrap_or_default(), err.code());
+        })
+    }
+
+    fn usage(&self) -> Vec<ClientUsage> {
+        let mut usage = self.clients.values().map(ApiClient::usage).collect::<Vec<_>>();
+        usage.sort_by(|left, right| left.name().cmp(right.name()));
+        usage
+    }
+}
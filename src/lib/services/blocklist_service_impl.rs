@@ -0,0 +1,214 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use tokio::{task, time};
+
+use crate::infrastructure::di_container::{BLOCKLIST_STORE_DEP, CONFIG_DEP, DIContainer, index_processor_dep};
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::models::blocked_id::BlockedId;
+use crate::models::entity::Entity;
+use crate::models::search_error::SearchError;
+use crate::services::blocklist_store::BlocklistStore;
+use crate::services::index_processor::IndexProcessor;
+
+/// Keeps taken down documents out of search: blocked ids are deleted from the live indexes right away,
+/// skipped by every later build and filtered from every search, which also covers restored snapshots.
+pub struct BlocklistServiceImpl {
+    store: Option<Arc<dyn BlocklistStore>>,
+    processors: HashMap<Entity, Arc<IndexProcessor>>,
+    blocked: RwLock<Arc<BlockedIds>>,
+    reload_interval: Duration,
+    reloader: Mutex<Option<JoinHandle<()>>>,
+    stopping: ShutdownHandle,
+}
+
+#[derive(Default)]
+struct BlockedIds {
+    entries: Vec<BlockedId>,
+    ids: HashMap<Entity, HashSet<u64>>,
+}
+
+impl BlockedIds {
+    fn new(entries: Vec<BlockedId>) -> Self {
+        let mut ids: HashMap<Entity, HashSet<u64>> = HashMap::new();
+        for entry in &entries {
+            match Entity::try_from(entry.entity()) {
+                Ok(entity) => {
+                    ids.entry(entity).or_default().insert(entry.id());
+                }
+                Err(_) => log::warn!("skipping blocked id {} of unknown {}", entry.id(), entry.entity()),
+            }
+        }
+        Self { entries, ids }
+    }
+}
+
+impl BlocklistServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let blocklist = config.blocklist();
+        let store = if blocklist.enabled() { Some(di_container.get(BLOCKLIST_STORE_DEP)?) } else { None };
+
+        let mut processors = HashMap::new();
+        for entity in Entity::all() {
+            processors.insert(entity, di_container.get(index_processor_dep(entity))?);
+        }
+
+        Ok(Self {
+            store,
+            processors,
+            blocked: RwLock::new(Arc::new(BlockedIds::default())),
+            reload_interval: Duration::from_secs(blocklist.reload_interval()),
+            reloader: Mutex::new(None),
+            stopping: ShutdownHandle::new(),
+        })
+    }
+
+    /// Replaces the enforced ids with the stored ones, deleting the newly blocked ones from the live indexes.
+    pub async fn reload(&self) -> anyhow::Result<()> {
+        self.reload_after(None).await
+    }
+
+    /// Same as [`BlocklistServiceImpl::reload`], skipping the ids of an entity already deleted by the caller.
+    async fn reload_after(&self, deleted: Option<(Entity, &[u64])>) -> anyhow::Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+
+        let loaded = BlockedIds::new(store.load().await?);
+        let current = self.current();
+        for (entity, ids) in &loaded.ids {
+            let added = ids.iter()
+                .filter(|id| !current.ids.get(entity).is_some_and(|current| current.contains(id)))
+                .filter(|id| !deleted.is_some_and(|(deleted_entity, deleted)| deleted_entity == *entity && deleted.contains(id)))
+                .copied()
+                .collect::<Vec<_>>();
+            if !added.is_empty() {
+                log::info!("removing {} blocked {} documents", added.len(), <&str>::from(*entity));
+                self.delete_from_index(*entity, added).await?;
+            }
+        }
+        *self.blocked.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(loaded);
+        Ok(())
+    }
+
+    /// Spawns the periodic reload, a no-op when the blocklist is disabled or the reload already runs.
+    pub fn start(self: &Arc<Self>) {
+        let mut reloader = self.reloader.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if self.store.is_none() || reloader.is_some() {
+            return;
+        }
+
+        let service = self.clone();
+        *reloader = Some(tokio::spawn(async move {
+            let mut interval = time::interval(service.reload_interval);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(err) = service.reload().await {
+                            log::warn!("failed reloading blocklist, keeping the current one: {err:?}");
+                        }
+                    }
+                    _ = service.stopping.wait() => break,
+                }
+            }
+        }));
+    }
+
+    pub fn stop(&self) {
+        self.stopping.shutdown();
+    }
+
+    /// Blocked ids of `entity`, skipped when building its indexes.
+    pub fn blocked_ids(&self, entity: Entity) -> HashSet<u64> {
+        self.current().ids.get(&entity).cloned().unwrap_or_default()
+    }
+
+    fn current(&self) -> Arc<BlockedIds> {
+        self.blocked.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    fn store(&self) -> Result<&Arc<dyn BlocklistStore>, SearchError> {
+        self.store.as_ref().ok_or_else(|| SearchError::BlocklistDisabled)
+    }
+
+    async fn delete_from_index(&self, entity: Entity, ids: Vec<u64>) -> anyhow::Result<()> {
+        let Some(processor) = self.processors.get(&entity).cloned() else {
+            return Ok(());
+        };
+        // Index writes are blocking, keep them off the async workers
+        task::spawn_blocking(move || processor.delete_ids(&ids)).await
+            .map_err(|err| anyhow!("blocked ids delete panicked: {err}"))?
+    }
+}
+
+#[async_trait]
+pub trait BlocklistService {
+    fn enabled(&self) -> bool;
+
+    fn entries(&self) -> Vec<BlockedId>;
+
+    /// Blocks `ids` and deletes them from the live indexes of `entity`, answering their stored entries.
+    /// Ids already blocked keep their stored reason and time.
+    async fn block(&self, entity: Entity, ids: &[u64], reason: Option<String>) -> Result<Vec<BlockedId>, SearchError>;
+
+    /// Whether the id was blocked. The document comes back with the next build of its index.
+    async fn unblock(&self, entity: Entity, id: u64) -> Result<bool, SearchError>;
+
+    /// Removes the blocked ids of `entity` from `results`.
+    fn filter(&self, entity: Entity, results: &mut Vec<u64>);
+}
+
+#[async_trait]
+impl BlocklistService for BlocklistServiceImpl {
+    fn enabled(&self) -> bool {
+        self.store.is_some()
+    }
+
+    fn entries(&self) -> Vec<BlockedId> {
+        self.current().entries.clone()
+    }
+
+    async fn block(&self, entity: Entity, ids: &[u64], reason: Option<String>) -> Result<Vec<BlockedId>, SearchError> {
+        let store = self.store()?;
+        if ids.is_empty() {
+            return Err(SearchError::InvalidPayload("ids must not be empty".to_string()));
+        }
+
+        let now = Utc::now();
+        let blocked = ids.iter()
+            .map(|id| BlockedId::new(<&str>::from(entity).to_string(), *id, reason.clone(), now))
+            .collect::<Vec<_>>();
+        store.add(&blocked).await?;
+        // Deleted before reloading so the documents are gone even if the reload fails, the reload skips them
+        self.delete_from_index(entity, ids.to_vec()).await?;
+        self.reload_after(Some((entity, ids))).await?;
+        // Stored entries, so ids blocked before keep reporting their original reason and time
+        let entity = <&str>::from(entity);
+        Ok(self.current().entries.iter()
+            .filter(|entry| entry.entity() == entity && ids.contains(&entry.id()))
+            .cloned()
+            .collect())
+    }
+
+    async fn unblock(&self, entity: Entity, id: u64) -> Result<bool, SearchError> {
+        let removed = self.store()?.remove(<&str>::from(entity), id).await?;
+        self.reload().await?;
+        Ok(removed)
+    }
+
+    fn filter(&self, entity: Entity, results: &mut Vec<u64>) {
+        if self.store.is_none() {
+            return;
+        }
+        if let Some(ids) = self.current().ids.get(&entity) {
+            results.retain(|id| !ids.contains(id));
+        }
+    }
+}
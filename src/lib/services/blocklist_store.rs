@@ -0,0 +1,14 @@
+use axum::async_trait;
+
+use crate::models::blocked_id::BlockedId;
+
+#[async_trait]
+pub trait BlocklistStore: Send + Sync {
+    async fn load(&self) -> anyhow::Result<Vec<BlockedId>>;
+
+    /// Adds the ids not blocked yet.
+    async fn add(&self, blocked: &[BlockedId]) -> anyhow::Result<()>;
+
+    /// Whether the id was blocked.
+    async fn remove(&self, entity: &str, id: u64) -> anyhow::Result<bool>;
+}
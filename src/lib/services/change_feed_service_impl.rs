@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use axum::async_trait;
+use sqlx::{Pool, Postgres};
+use sqlx::postgres::PgListener;
+use tokio::task::{self, JoinHandle};
+use tokio::time::{self, Instant};
+
+use crate::handlers::language_negotiation::parse_language;
+use crate::infrastructure::di_container::{CONFIG_DEP, DB_POOL_DEP, DIContainer, doc_details_retriever_dep, DOCUMENT_SERVICE_IMPL_DEP, METRICS_DEP};
+use crate::infrastructure::metrics::Metrics;
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::models::change_event::ChangeEvent;
+use crate::models::doc_details::DocDetails;
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::services::doc_details_retriever::DocDetailsRetriever;
+use crate::services::document_service_impl::{DocumentService, DocumentServiceImpl};
+use crate::services::index_processor::IndexProcessor;
+use crate::services::index_task::IndexTask;
+use crate::services::indexer_runner::IndexerRunner;
+
+/// Notifications following one within this delay are applied with it.
+const BATCH_WINDOW: Duration = Duration::from_millis(50);
+const MAX_BATCH_SIZE: usize = 1000;
+
+/// Applies the changes announced on a Postgres notification channel to the live indexes, a burst of
+/// notifications at once. Notifications sent while the listener is disconnected are lost, so reconnections
+/// are followed by a full rebuild, run through the [`IndexerRunner`] so it never overlaps with a scheduled one.
+pub struct ChangeFeedServiceImpl {
+    enabled: bool,
+    channel: String,
+    reconnect_delay: Duration,
+    catch_up_interval: Duration,
+    last_catch_up: Mutex<Option<Instant>>,
+    db_pool: Arc<Pool<Postgres>>,
+    retrievers: HashMap<Entity, Arc<dyn DocDetailsRetriever>>,
+    index_tasks: Vec<IndexTask<IndexProcessor>>,
+    documents: Arc<DocumentServiceImpl>,
+    metrics: Arc<Metrics>,
+    listener: Mutex<Option<JoinHandle<()>>>,
+    stopping: ShutdownHandle,
+}
+
+/// Document named by a change event.
+struct Change {
+    entity: Entity,
+    language: Language,
+    id: u64,
+}
+
+/// Why the listener stopped receiving notifications.
+enum Disconnect {
+    Stopped,
+    Lost(anyhow::Error),
+}
+
+impl ChangeFeedServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let change_feed = config.change_feed();
+
+        let mut retrievers = HashMap::new();
+        let mut index_tasks = Vec::new();
+        for entity in Entity::all() {
+            retrievers.insert(entity, di_container.get(doc_details_retriever_dep(entity))?);
+            index_tasks.push(IndexerRunner::index_task(di_container, entity)?);
+        }
+
+        Ok(Self {
+            enabled: change_feed.enabled(),
+            channel: change_feed.channel().to_string(),
+            reconnect_delay: Duration::from_secs(change_feed.reconnect_delay()),
+            catch_up_interval: Duration::from_secs(change_feed.catch_up_interval()),
+            last_catch_up: Mutex::new(None),
+            db_pool: di_container.get(DB_POOL_DEP)?,
+            retrievers,
+            index_tasks,
+            documents: di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?,
+            metrics: di_container.get(METRICS_DEP)?,
+            listener: Mutex::new(None),
+            stopping: ShutdownHandle::new(),
+        })
+    }
+
+    /// Spawns the listener, a no-op when the change feed is disabled or the listener already runs.
+    pub fn start(self: &Arc<Self>, indexer_runner: Arc<IndexerRunner>) {
+        let mut listener = self.listener.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !self.enabled || listener.is_some() {
+            return;
+        }
+
+        let service = self.clone();
+        *listener = Some(tokio::spawn(async move {
+            let mut connected_before = false;
+            loop {
+                match service.listen(&indexer_runner, connected_before).await {
+                    Disconnect::Stopped => break,
+                    Disconnect::Lost(err) => {
+                        log::warn!("change feed disconnected, reconnecting in {}s: {err:?}", service.reconnect_delay.as_secs());
+                    }
+                }
+                connected_before = true;
+
+                tokio::select! {
+                    _ = time::sleep(service.reconnect_delay) => {}
+                    _ = service.stopping.wait() => break,
+                }
+            }
+        }));
+    }
+
+    pub fn stop(&self) {
+        self.stopping.shutdown();
+    }
+
+    /// Receives notifications until the connection is lost or the service stopped. On reconnections
+    /// the indexes are rebuilt once subscribed, so no change committed meanwhile is missed.
+    async fn listen(&self, indexer_runner: &IndexerRunner, reconnection: bool) -> Disconnect {
+        let mut listener = match self.subscribe().await {
+            Ok(listener) => listener,
+            Err(err) => return Disconnect::Lost(err),
+        };
+        log::info!("listening to change feed channel {}", self.channel);
+
+        if reconnection {
+            self.catch_up(indexer_runner).await;
+        }
+
+        loop {
+            let mut received = tokio::select! {
+                notification = listener.try_recv() => notification,
+                _ = self.stopping.wait() => return Disconnect::Stopped,
+            };
+
+            // The notifications of a burst are applied together, with a single write per index
+            let mut payloads = Vec::new();
+            let disconnect = loop {
+                match received {
+                    Ok(Some(notification)) => payloads.push(notification.payload().to_string()),
+                    Ok(None) => break Some(Disconnect::Lost(anyhow!("connection closed"))),
+                    Err(err) => break Some(Disconnect::Lost(err.into())),
+                }
+                if payloads.len() >= MAX_BATCH_SIZE {
+                    break None;
+                }
+                match time::timeout(BATCH_WINDOW, listener.try_recv()).await {
+                    Ok(notification) => received = notification,
+                    Err(_) => break None,
+                }
+            };
+
+            if let Err(err) = self.apply_batch(&payloads).await {
+                log::warn!("skipping {} change events: {err:?}", payloads.len());
+            }
+            if let Some(disconnect) = disconnect {
+                return disconnect;
+            }
+        }
+    }
+
+    async fn subscribe(&self) -> anyhow::Result<PgListener> {
+        let mut listener = PgListener::connect_with(&self.db_pool).await?;
+        listener.listen(&self.channel).await?;
+        Ok(listener)
+    }
+
+    /// Rebuilds every index, at most once every `catch_up_interval`: a catch up requested sooner first waits
+    /// for the interval to elapse, picking up every change missed meanwhile.
+    pub async fn catch_up(&self, indexer_runner: &IndexerRunner) {
+        let last_catch_up = *self.last_catch_up.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let wait = last_catch_up
+            .map(|last_catch_up| (last_catch_up + self.catch_up_interval).saturating_duration_since(Instant::now()))
+            .unwrap_or_default();
+        if !wait.is_zero() {
+            log::info!("delaying the change feed catch up by {}s, the last one is too recent", wait.as_secs());
+            tokio::select! {
+                _ = time::sleep(wait) => {}
+                _ = self.stopping.wait() => return,
+            }
+        }
+        *self.last_catch_up.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Instant::now());
+
+        log::info!("rebuilding every index to catch up with the changes missed while disconnected");
+        self.metrics.observe_change_feed_catch_up();
+        for index_task in &self.index_tasks {
+            if let Err(err) = indexer_runner.reindex(index_task).await {
+                log::warn!("change feed catch up failed, waiting for the next scheduled reindex: {err:?}");
+            }
+        }
+    }
+
+    fn parse(&self, payload: &str) -> anyhow::Result<Change> {
+        let event: ChangeEvent = serde_json::from_str(payload).context("invalid change event")?;
+        let entity = Entity::try_from(event.entity().to_ascii_uppercase().as_str())
+            .map_err(|_| anyhow!("unknown content type '{}'", event.entity()))?;
+        let language = parse_language(event.language())?;
+        Ok(Change { entity, language, id: event.id() })
+    }
+
+    /// Upserts or deletes the documents of `changes` with a single write per content type and language.
+    async fn apply_changes(&self, changes: Vec<Change>) -> anyhow::Result<()> {
+        let mut grouped: HashMap<(Entity, Language), HashSet<u64>> = HashMap::new();
+        for change in &changes {
+            grouped.entry((change.entity, change.language)).or_default().insert(change.id);
+        }
+
+        let mut writes = Vec::new();
+        for ((entity, language), ids) in grouped {
+            let retriever = self.retrievers.get(&entity).ok_or_else(|| anyhow!("no data source for {}", <&str>::from(entity)))?;
+            let ids = ids.into_iter().collect::<Vec<_>>();
+            let current = retriever.retrieve_ids(language.into(), &ids).await?;
+            let found = current.iter().map(DocDetails::id).collect::<HashSet<_>>();
+            let missing = ids.into_iter().filter(|id| !found.contains(id)).collect::<Vec<_>>();
+            writes.push((entity, language, current, missing));
+        }
+
+        let documents = self.documents.clone();
+        // Index writes are blocking, keep them off the async workers
+        task::spawn_blocking(move || {
+            for (entity, language, current, missing) in writes {
+                if !current.is_empty() {
+                    documents.upsert(entity, language, current)?;
+                }
+                if !missing.is_empty() {
+                    documents.delete(entity, language, &missing)?;
+                }
+            }
+            anyhow::Ok(())
+        }).await??;
+        log::debug!("applied {} change events", changes.len());
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait ChangeFeedService {
+    fn enabled(&self) -> bool;
+
+    /// Upserts the document named by the `payload` change event with its current data source content,
+    /// or deletes it when it is not in the data source anymore.
+    async fn apply(&self, payload: &str) -> anyhow::Result<()>;
+
+    /// Same as [`ChangeFeedService::apply`] for every change event of `payloads` at once, skipping the
+    /// invalid ones.
+    async fn apply_batch(&self, payloads: &[String]) -> anyhow::Result<()>;
+}
+
+#[async_trait]
+impl ChangeFeedService for ChangeFeedServiceImpl {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn apply(&self, payload: &str) -> anyhow::Result<()> {
+        let change = self.parse(payload)?;
+        self.apply_changes(vec![change]).await
+    }
+
+    async fn apply_batch(&self, payloads: &[String]) -> anyhow::Result<()> {
+        let changes = payloads.iter()
+            .filter_map(|payload| self.parse(payload)
+                .inspect_err(|err| log::warn!("skipping change event '{payload}': {err:?}"))
+                .ok())
+            .collect::<Vec<_>>();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        self.apply_changes(changes).await
+    }
+}
@@ -0,0 +1,14 @@
+use axum::async_trait;
+
+use crate::models::curation_rule::CurationRule;
+
+#[async_trait]
+pub trait CurationRuleStore: Send + Sync {
+    async fn load(&self) -> anyhow::Result<Vec<CurationRule>>;
+
+    /// Creates the rule or replaces the one with the same id.
+    async fn save(&self, rule: &CurationRule) -> anyhow::Result<()>;
+
+    /// Whether a rule with `id` existed.
+    async fn delete(&self, id: &str) -> anyhow::Result<bool>;
+}
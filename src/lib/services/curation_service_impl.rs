@@ -0,0 +1,228 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use axum::async_trait;
+use tokio::task::JoinHandle;
+use tokio::time;
+
+use crate::infrastructure::di_container::{CONFIG_DEP, CURATION_RULE_STORE_DEP, DIContainer};
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::models::curation_rule::{CurationAction, CurationRule};
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::models::search_error::SearchError;
+use crate::models::search_event::SearchEvent;
+use crate::services::curation_rule_store::CurationRuleStore;
+
+/// Serves the editorial rules from memory, reloaded from the store periodically and after every
+/// change made through this instance.
+pub struct CurationServiceImpl {
+    store: Option<Arc<dyn CurationRuleStore>>,
+    rules: RwLock<Arc<CompiledRules>>,
+    reload_interval: Duration,
+    reloader: Mutex<Option<JoinHandle<()>>>,
+    stopping: ShutdownHandle,
+}
+
+/// Action of a rule, restricted to a language when set.
+type LanguageAction = (Option<Language>, CurationAction);
+
+#[derive(Default)]
+struct CompiledRules {
+    rules: Vec<CurationRule>,
+    actions: HashMap<(Entity, String), Vec<LanguageAction>>,
+    fingerprint: u64,
+}
+
+impl CompiledRules {
+    fn new(rules: Vec<CurationRule>) -> Self {
+        let mut actions: HashMap<_, Vec<_>> = HashMap::new();
+        for rule in &rules {
+            let entity = Entity::try_from(rule.entity().to_ascii_uppercase().as_str());
+            let language = rule.language().map(Language::from_tag);
+            let (Ok(entity), None | Some(Some(_))) = (entity, language) else {
+                log::warn!("skipping curation rule {} of unknown {} {}", rule.id(), rule.entity(), rule.language().unwrap_or_default());
+                continue;
+            };
+            // Rules edited by hand may not be normalized
+            actions.entry((entity, SearchEvent::normalize(rule.query())))
+                .or_default()
+                .push((language.flatten(), rule.action().clone()));
+        }
+
+        let fingerprint = if rules.is_empty() {
+            0
+        } else {
+            let mut hasher = DefaultHasher::new();
+            serde_json::to_string(&rules).unwrap_or_default().hash(&mut hasher);
+            hasher.finish()
+        };
+        Self { rules, actions, fingerprint }
+    }
+}
+
+impl CurationServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let curation = config.curation();
+        let store = if curation.enabled() { Some(di_container.get(CURATION_RULE_STORE_DEP)?) } else { None };
+
+        Ok(Self {
+            store,
+            rules: RwLock::new(Arc::new(CompiledRules::default())),
+            reload_interval: Duration::from_secs(curation.reload_interval()),
+            reloader: Mutex::new(None),
+            stopping: ShutdownHandle::new(),
+        })
+    }
+
+    /// Replaces the served rules with the stored ones, answers whether they changed.
+    pub async fn reload(&self) -> anyhow::Result<bool> {
+        let Some(store) = &self.store else {
+            return Ok(false);
+        };
+
+        let rules = store.load().await?;
+        if rules == self.compiled().rules {
+            return Ok(false);
+        }
+        log::info!("serving {} curation rules", rules.len());
+        *self.rules.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(CompiledRules::new(rules));
+        Ok(true)
+    }
+
+    /// Spawns the periodic reload, a no-op when curation is disabled or the reload already runs.
+    pub fn start(self: &Arc<Self>) {
+        let mut reloader = self.reloader.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if self.store.is_none() || reloader.is_some() {
+            return;
+        }
+
+        let service = self.clone();
+        *reloader = Some(tokio::spawn(async move {
+            let mut interval = time::interval(service.reload_interval);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(err) = service.reload().await {
+                            log::warn!("failed reloading curation rules, keeping the current ones: {err:?}");
+                        }
+                    }
+                    _ = service.stopping.wait() => break,
+                }
+            }
+        }));
+    }
+
+    pub fn stop(&self) {
+        self.stopping.shutdown();
+    }
+
+    fn compiled(&self) -> Arc<CompiledRules> {
+        self.rules.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    fn store(&self) -> Result<&Arc<dyn CurationRuleStore>, SearchError> {
+        self.store.as_ref().ok_or_else(|| SearchError::CurationDisabled)
+    }
+
+    /// Rule with canonical entity and language codes and a normalized query.
+    fn validate(rule: &CurationRule) -> Result<CurationRule, SearchError> {
+        let entity = Entity::try_from(rule.entity().to_ascii_uppercase().as_str())
+            .map_err(|_| SearchError::UnknownEntity(rule.entity().to_string()))?;
+        let language = rule.language()
+            .map(|language| Language::from_tag(language).ok_or_else(|| SearchError::InvalidLanguage(language.to_string())))
+            .transpose()?;
+        let query = SearchEvent::normalize(rule.query());
+        if query.is_empty() {
+            return Err(SearchError::EmptyQuery);
+        }
+        if rule.action().ids().is_empty() {
+            return Err(SearchError::InvalidRule("ids must not be empty".to_string()));
+        }
+        if rule.id().is_empty() {
+            return Err(SearchError::InvalidRule("id must not be empty".to_string()));
+        }
+
+        Ok(CurationRule::new(
+            rule.id().to_string(),
+            query,
+            <&str>::from(entity).to_string(),
+            language.map(|language| <&str>::from(language).to_string()),
+            rule.action().clone(),
+        ))
+    }
+}
+
+#[async_trait]
+pub trait CurationService {
+    fn enabled(&self) -> bool;
+
+    fn rules(&self) -> Vec<CurationRule>;
+
+    /// Creates or replaces a rule, answering it as stored.
+    async fn save(&self, rule: CurationRule) -> Result<CurationRule, SearchError>;
+
+    /// Whether a rule with `id` existed.
+    async fn delete(&self, id: &str) -> Result<bool, SearchError>;
+
+    /// Applies the rules of `query`, already normalized, to `results`: boosts first, then pins and
+    /// finally hides, so takedowns always win.
+    fn apply(&self, entity: Entity, lang: Language, query: &str, results: &mut Vec<u64>);
+
+    /// Identifier of the served rules, 0 when there are none.
+    fn fingerprint(&self) -> u64;
+}
+
+#[async_trait]
+impl CurationService for CurationServiceImpl {
+    fn enabled(&self) -> bool {
+        self.store.is_some()
+    }
+
+    fn rules(&self) -> Vec<CurationRule> {
+        self.compiled().rules.clone()
+    }
+
+    async fn save(&self, rule: CurationRule) -> Result<CurationRule, SearchError> {
+        let store = self.store()?;
+        let rule = Self::validate(&rule)?;
+        store.save(&rule).await?;
+        self.reload().await?;
+        Ok(rule)
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, SearchError> {
+        let deleted = self.store()?.delete(id).await?;
+        self.reload().await?;
+        Ok(deleted)
+    }
+
+    fn apply(&self, entity: Entity, lang: Language, query: &str, results: &mut Vec<u64>) {
+        if self.store.is_none() {
+            return;
+        }
+        let compiled = self.compiled();
+        let Some(actions) = compiled.actions.get(&(entity, query.to_string())) else {
+            return;
+        };
+
+        let matching = actions.iter()
+            .filter(|(language, _)| language.map_or(true, |language| language == lang))
+            .map(|(_, action)| action)
+            .collect::<Vec<_>>();
+        for kind in ["boost", "pin", "hide"] {
+            matching.iter()
+                .filter(|action| action.name() == kind)
+                .for_each(|action| action.apply(results));
+        }
+    }
+
+    fn fingerprint(&self) -> u64 {
+        self.compiled().fingerprint
+    }
+}
@@ -3,6 +3,9 @@ use axum::async_trait;
 use crate::models::doc_details::DocDetails;
 
 #[async_trait]
-pub trait DocDetailsRetriever {
+pub trait DocDetailsRetriever: Send + Sync {
     async fn retrieve(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<DocDetails>>;
+
+    /// Current content of the documents with one of `ids` in `lang`, missing ids are not in the source anymore.
+    async fn retrieve_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<DocDetails>>;
 }
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::infrastructure::di_container::{BLOCKLIST_SERVICE_IMPL_DEP, DIContainer, index_processor_dep, METRICS_DEP};
+use crate::infrastructure::metrics::Metrics;
+use crate::models::doc_details::DocDetails;
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::models::search_error::SearchError;
+use crate::services::blocklist_service_impl::BlocklistServiceImpl;
+use crate::services::index_processor::{IndexProcessor, IndexWriter};
+
+const UPSERT_OPERATION: &str = "upsert";
+const DELETE_OPERATION: &str = "delete";
+
+/// Writes documents published between builds straight into the live indexes. Writes are not
+/// persisted: the source of truth stays the data source, read again by the next build.
+pub struct DocumentServiceImpl {
+    processors: HashMap<Entity, Arc<IndexProcessor>>,
+    blocklist: Arc<BlocklistServiceImpl>,
+    metrics: Arc<Metrics>,
+}
+
+impl DocumentServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let mut processors = HashMap::new();
+        for entity in Entity::all() {
+            processors.insert(entity, di_container.get(index_processor_dep(entity))?);
+        }
+
+        Ok(Self {
+            processors,
+            blocklist: di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?,
+            metrics: di_container.get(METRICS_DEP)?,
+        })
+    }
+
+    fn processor(&self, entity: Entity) -> Result<&Arc<IndexProcessor>, SearchError> {
+        self.processors.get(&entity)
+            .ok_or_else(|| SearchError::SearcherNotFound(<&str>::from(entity).to_string()))
+    }
+}
+
+pub trait DocumentService {
+    /// Adds or replaces `documents` in the index of `entity` and `lang`, skipping blocked ids.
+    /// Answers the skipped ids.
+    fn upsert(&self, entity: Entity, lang: Language, documents: Vec<DocDetails>) -> Result<Vec<u64>, SearchError>;
+
+    fn delete(&self, entity: Entity, lang: Language, ids: &[u64]) -> Result<(), SearchError>;
+}
+
+impl DocumentService for DocumentServiceImpl {
+    fn upsert(&self, entity: Entity, lang: Language, mut documents: Vec<DocDetails>) -> Result<Vec<u64>, SearchError> {
+        let processor = self.processor(entity)?;
+        let blocked_ids = self.blocklist.blocked_ids(entity);
+        let mut blocked = Vec::new();
+        documents.retain(|doc| {
+            let allowed = !blocked_ids.contains(&doc.id());
+            if !allowed {
+                blocked.push(doc.id());
+            }
+            allowed
+        });
+
+        processor.upsert(lang, &documents)?;
+        self.metrics.observe_documents_written(entity, lang, UPSERT_OPERATION, documents.len());
+        Ok(blocked)
+    }
+
+    fn delete(&self, entity: Entity, lang: Language, ids: &[u64]) -> Result<(), SearchError> {
+        self.processor(entity)?.delete(lang, ids)?;
+        self.metrics.observe_documents_written(entity, lang, DELETE_OPERATION, ids.len());
+        Ok(())
+    }
+}
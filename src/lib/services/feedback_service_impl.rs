@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use moka::sync::Cache;
+use tokio::task::{self, JoinHandle};
+use tokio::time;
+
+use crate::infrastructure::di_container::{CONFIG_DEP, DIContainer, index_processor_dep, METRICS_DEP};
+use crate::infrastructure::metrics::Metrics;
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::models::click_aggregate::{ClickAggregate, DecayedCount};
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::models::search_error::SearchError;
+use crate::models::search_event::SearchEvent;
+use crate::services::index_processor::{IndexProcessor, IndexSearcher, LIMIT_RESULT_SIZE};
+
+/// Decayed weight under which a click aggregate is forgotten on the next save.
+const MIN_WEIGHT: f64 = 0.01;
+/// Clicked results kept per query, a search never shows more, the least clicked one is forgotten beyond it.
+const MAX_CLICKED_IDS: usize = LIMIT_RESULT_SIZE;
+
+/// Aggregates the results selected by users per content type, language and normalized query, and
+/// turns them into score boosts for the same query. Aggregates are kept in memory, bounded to the
+/// configured number of queries, saved to a JSON file periodically and at shutdown, and loaded back at boot.
+pub struct FeedbackServiceImpl {
+    enabled: bool,
+    path: PathBuf,
+    half_life: Duration,
+    boost: f32,
+    flush_interval: Duration,
+    clicks: Cache<ClickKey, Arc<Mutex<QueryClicks>>>,
+    /// Counts every click since boot, versions are drawn from it so a forgotten query never reuses one.
+    clock: AtomicU64,
+    processors: HashMap<Entity, Arc<IndexProcessor>>,
+    dirty: AtomicBool,
+    listeners: RwLock<Vec<ClickListener>>,
+    flusher: Mutex<Option<JoinHandle<()>>>,
+    stopping: ShutdownHandle,
+    metrics: Arc<Metrics>,
+}
+
+type ClickListener = Box<dyn Fn(Entity, Language, &str) + Send + Sync>;
+
+#[derive(Hash, PartialEq, Eq, Clone)]
+struct ClickKey {
+    entity: Entity,
+    language: Language,
+    query: String,
+}
+
+#[derive(Default)]
+struct QueryClicks {
+    /// Identifies the boosts of the query, 0 until a click is recorded since boot.
+    version: u64,
+    counts: HashMap<u64, DecayedCount>,
+}
+
+impl FeedbackServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let config = di_container.get(CONFIG_DEP)?;
+        let feedback = config.feedback();
+        let path = PathBuf::from(feedback.path());
+        let clicks = Cache::builder().max_capacity(feedback.max_queries()).build();
+        if feedback.enabled() {
+            Self::load(&path, &clicks)?;
+        }
+
+        let mut processors = HashMap::new();
+        for entity in Entity::all() {
+            processors.insert(entity, di_container.get(index_processor_dep(entity))?);
+        }
+
+        Ok(Self {
+            enabled: feedback.enabled(),
+            path,
+            half_life: Duration::from_secs(feedback.half_life_hours() * 3600),
+            boost: feedback.boost(),
+            flush_interval: Duration::from_secs(feedback.flush_interval()),
+            clicks,
+            clock: AtomicU64::new(0),
+            processors,
+            dirty: AtomicBool::new(false),
+            listeners: RwLock::new(Vec::new()),
+            flusher: Mutex::new(None),
+            stopping: ShutdownHandle::new(),
+            metrics: di_container.get(METRICS_DEP)?,
+        })
+    }
+
+    /// Registers a callback invoked with the content type, language and normalized query of every click.
+    pub fn on_click(&self, listener: impl Fn(Entity, Language, &str) + Send + Sync + 'static) {
+        if let Ok(mut listeners) = self.listeners.write() {
+            listeners.push(Box::new(listener));
+        }
+    }
+
+    /// Spawns the periodic save of the aggregates, a no-op when feedback is disabled or already saved.
+    pub fn start(self: &Arc<Self>) {
+        let mut flusher = self.flusher.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !self.enabled || flusher.is_some() {
+            return;
+        }
+
+        let service = self.clone();
+        *flusher = Some(tokio::spawn(async move {
+            let mut interval = time::interval(service.flush_interval);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => service.clone().save_in_background().await,
+                    _ = service.stopping.wait() => break,
+                }
+            }
+            service.clone().save_in_background().await;
+        }));
+    }
+
+    /// Saves the aggregates a last time and stops the periodic save, waiting at most `timeout`.
+    pub async fn stop(&self, timeout: Duration) {
+        self.stopping.shutdown();
+        let flusher = self.flusher.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        if let Some(flusher) = flusher {
+            log::info!("saving click feedback...");
+            if time::timeout(timeout, flusher).await.is_err() {
+                log::warn!("click feedback save exceeded {}s, recent clicks are lost", timeout.as_secs());
+            }
+        }
+    }
+
+    /// Writes every aggregate still weighing at least [`MIN_WEIGHT`] to the configured file, forgetting
+    /// the others. The file is replaced atomically so a crash never leaves it truncated.
+    pub fn save(&self) -> anyhow::Result<()> {
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let mut aggregates = Vec::new();
+        for (key, clicks) in self.clicks.iter() {
+            let mut clicks = clicks.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            clicks.counts.retain(|_, count| count.weight_at(now, self.half_life) >= MIN_WEIGHT);
+            if clicks.counts.is_empty() {
+                self.clicks.invalidate(key.as_ref());
+                continue;
+            }
+            aggregates.extend(clicks.counts.iter()
+                .map(|(id, count)| ClickAggregate::new(
+                    <&str>::from(key.entity).to_string(),
+                    <&str>::from(key.language).to_string(),
+                    key.query.clone(),
+                    *id,
+                    *count)));
+        }
+
+        let result = Self::write(&self.path, &aggregates);
+        if result.is_err() {
+            self.dirty.store(true, Ordering::Release);
+        }
+        result
+    }
+
+    async fn save_in_background(self: Arc<Self>) {
+        match task::spawn_blocking(move || self.save()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => log::warn!("failed saving click feedback: {err:?}"),
+            Err(err) => log::warn!("click feedback save panicked: {err}"),
+        }
+    }
+
+    fn write(path: &Path, aggregates: &[ClickAggregate]) -> anyhow::Result<()> {
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec(aggregates)?)
+            .with_context(|| format!("failed writing {}", temp_path.display()))?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("failed replacing {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Clicked queries kept in memory. Pending evictions are applied first since the count is otherwise
+    /// only eventually consistent.
+    pub fn query_count(&self) -> u64 {
+        self.clicks.run_pending_tasks();
+        self.clicks.entry_count()
+    }
+
+    fn load(path: &Path, clicks: &Cache<ClickKey, Arc<Mutex<QueryClicks>>>) -> anyhow::Result<()> {
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(anyhow::Error::from(err).context(format!("failed reading {}", path.display()))),
+        };
+
+        let aggregates: Vec<ClickAggregate> = serde_json::from_slice(&content)
+            .with_context(|| format!("failed parsing click feedback {}", path.display()))?;
+        let total = aggregates.len();
+        for aggregate in aggregates {
+            let (Ok(entity), Ok(language)) = (Entity::try_from(aggregate.entity()), Language::try_from(aggregate.language())) else {
+                log::warn!("skipping click feedback of unknown {} {}", aggregate.entity(), aggregate.language());
+                continue;
+            };
+            let key = ClickKey { entity, language, query: aggregate.query().to_string() };
+            clicks.get_with(key, Default::default)
+                .lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+                .counts.insert(aggregate.id(), aggregate.count());
+        }
+        log::info!("loaded {total} click feedback aggregates from {}", path.display());
+        Ok(())
+    }
+
+    /// Clicks of `query`, `None` when it was never clicked or was forgotten.
+    fn query_clicks(&self, entity: Entity, lang: Language, query: &str) -> Option<Arc<Mutex<QueryClicks>>> {
+        self.clicks.get(&ClickKey { entity, language: lang, query: query.to_string() })
+    }
+}
+
+pub trait FeedbackService {
+    fn enabled(&self) -> bool;
+
+    /// Records that the result `id` was selected among the results of `keywords`, rejecting ids missing
+    /// from the index.
+    fn record_click(&self, entity: Entity, lang: Language, keywords: &str, id: u64) -> Result<(), SearchError>;
+
+    /// Score multiplier of every clicked result of `query`, already normalized, empty when feedback
+    /// is disabled or the query was never clicked.
+    fn boosts(&self, entity: Entity, lang: Language, query: &str) -> HashMap<u64, f32>;
+
+    /// Identifier of the clicks of `query`, already normalized, changing whenever one is recorded. 0 when
+    /// no click was recorded since boot or the query was forgotten.
+    fn version(&self, entity: Entity, lang: Language, query: &str) -> u64;
+}
+
+impl FeedbackService for FeedbackServiceImpl {
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn record_click(&self, entity: Entity, lang: Language, keywords: &str, id: u64) -> Result<(), SearchError> {
+        if !self.enabled {
+            return Err(SearchError::FeedbackDisabled);
+        }
+        let query = SearchEvent::normalize(keywords);
+        if query.is_empty() {
+            return Err(SearchError::EmptyQuery);
+        }
+
+        let indexed = match self.processors.get(&entity) {
+            Some(processor) => processor.contains(lang, id)?,
+            None => false,
+        };
+        if !indexed {
+            return Err(SearchError::UnknownDocument(id));
+        }
+
+        let now = Utc::now();
+        let key = ClickKey { entity, language: lang, query };
+        {
+            let clicks = self.clicks.get_with(key.clone(), Default::default);
+            let mut clicks = clicks.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if !clicks.counts.contains_key(&id) && clicks.counts.len() >= MAX_CLICKED_IDS {
+                let lightest = clicks.counts.iter()
+                    .min_by(|(_, left), (_, right)| left.weight_at(now, self.half_life).total_cmp(&right.weight_at(now, self.half_life)))
+                    .map(|(id, _)| *id);
+                if let Some(lightest) = lightest {
+                    clicks.counts.remove(&lightest);
+                }
+            }
+            clicks.version = self.clock.fetch_add(1, Ordering::Relaxed) + 1;
+            clicks.counts.entry(id)
+                .or_insert_with(|| DecayedCount::new(0.0, now))
+                .add(1.0, now, self.half_life);
+        }
+        self.dirty.store(true, Ordering::Release);
+        self.metrics.observe_click(entity, lang);
+
+        if let Ok(listeners) = self.listeners.read() {
+            listeners.iter().for_each(|listener| listener(entity, lang, &key.query));
+        }
+        Ok(())
+    }
+
+    fn boosts(&self, entity: Entity, lang: Language, query: &str) -> HashMap<u64, f32> {
+        if !self.enabled {
+            return HashMap::new();
+        }
+        let Some(clicks) = self.query_clicks(entity, lang, query) else {
+            return HashMap::new();
+        };
+
+        let now = Utc::now();
+        let clicks = clicks.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        clicks.counts.iter()
+            .map(|(id, count)| (*id, 1.0 + self.boost * count.weight_at(now, self.half_life).ln_1p() as f32))
+            .collect()
+    }
+
+    fn version(&self, entity: Entity, lang: Language, query: &str) -> u64 {
+        self.query_clicks(entity, lang, query)
+            .map(|clicks| clicks.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).version)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::async_trait;
+use sqlx::{Pool, Postgres, query};
+use tokio::time;
+
+use crate::config::Config;
+use crate::infrastructure::di_container::{CONFIG_DEP, DB_POOL_DEP, DIContainer, GAME_INDEX_PROCESSOR_DEP, MOVIE_INDEX_PROCESSOR_DEP, RECIPE_INDEX_PROCESSOR_DEP, TV_INDEX_PROCESSOR_DEP};
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::models::readiness::{IndexReadiness, Readiness};
+use crate::services::index_processor::IndexProcessor;
+
+const DATABASE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct HealthServiceImpl {
+    config: Arc<Config>,
+    db_pool: Arc<Pool<Postgres>>,
+    index_processors: HashMap<Entity, Arc<IndexProcessor>>,
+}
+
+impl HealthServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let mut index_processors = HashMap::new();
+        index_processors.insert(Entity::Movie, di_container.get(MOVIE_INDEX_PROCESSOR_DEP)?);
+        index_processors.insert(Entity::Tv, di_container.get(TV_INDEX_PROCESSOR_DEP)?);
+        index_processors.insert(Entity::Recipe, di_container.get(RECIPE_INDEX_PROCESSOR_DEP)?);
+        index_processors.insert(Entity::Game, di_container.get(GAME_INDEX_PROCESSOR_DEP)?);
+
+        Ok(Self {
+            config: di_container.get(CONFIG_DEP)?,
+            db_pool: di_container.get(DB_POOL_DEP)?,
+            index_processors,
+        })
+    }
+
+    async fn database_ready(&self) -> Option<bool> {
+        if !self.config.uses_database() {
+            return None;
+        }
+        let check = query("SELECT 1").execute(&*self.db_pool);
+        Some(matches!(time::timeout(DATABASE_CHECK_TIMEOUT, check).await, Ok(Ok(_))))
+    }
+}
+
+#[async_trait]
+pub trait HealthService {
+    async fn readiness(&self) -> Readiness;
+}
+
+#[async_trait]
+impl HealthService for HealthServiceImpl {
+    async fn readiness(&self) -> Readiness {
+        let mut indexes = Vec::new();
+        for entity in Entity::all() {
+            let index_processor = &self.index_processors[&entity];
+            for language in Language::all() {
+                indexes.push(IndexReadiness::new(entity, language, index_processor.last_build(language), index_processor.is_stale(language)));
+            }
+        }
+
+        // Persisted indexes keep being served during a database outage
+        let database_required = self.config.snapshot().persist_path().is_none();
+        Readiness::new(self.database_ready().await, database_required, indexes)
+    }
+}
@@ -1,4 +1,12 @@
 pub mod movie_doc_details_retriever;
 pub mod tv_doc_details_retriever;
 pub mod game_doc_details_retriever;
-pub mod recipe_doc_details_retriever;
\ No newline at end of file
+pub mod recipe_doc_details_retriever;
+pub mod in_memory_doc_details_retriever;
+pub mod file_doc_details_retriever;
+pub mod database_search_event_sink;
+pub mod file_search_event_sink;
+pub mod database_curation_rule_store;
+pub mod file_curation_rule_store;
+pub mod database_blocklist_store;
+pub mod file_blocklist_store;
\ No newline at end of file
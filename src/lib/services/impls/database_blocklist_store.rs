@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use tokio::sync::OnceCell;
+
+use crate::infrastructure::di_container::{BLOCKED_ID_REPOSITORY_DEP, DIContainer};
+use crate::models::blocked_id::BlockedId;
+use crate::repositories::blocked_id_repository_impl::BlockedIdRepository;
+use crate::services::blocklist_store::BlocklistStore;
+
+pub struct DatabaseBlocklistStore {
+    repository: Arc<dyn BlockedIdRepository>,
+    table_created: OnceCell<()>,
+}
+
+impl DatabaseBlocklistStore {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        Ok(Self { repository: di_container.get(BLOCKED_ID_REPOSITORY_DEP)?, table_created: OnceCell::new() })
+    }
+}
+
+#[async_trait]
+impl BlocklistStore for DatabaseBlocklistStore {
+    async fn load(&self) -> anyhow::Result<Vec<BlockedId>> {
+        // Retried on the next access when the database is unavailable
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.find_all().await
+    }
+
+    async fn add(&self, blocked: &[BlockedId]) -> anyhow::Result<()> {
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.insert(blocked).await
+    }
+
+    async fn remove(&self, entity: &str, id: u64) -> anyhow::Result<bool> {
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.delete(entity, id).await
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use tokio::sync::OnceCell;
+
+use crate::infrastructure::di_container::{CURATION_RULE_REPOSITORY_DEP, DIContainer};
+use crate::models::curation_rule::CurationRule;
+use crate::repositories::curation_rule_repository_impl::CurationRuleRepository;
+use crate::services::curation_rule_store::CurationRuleStore;
+
+pub struct DatabaseCurationRuleStore {
+    repository: Arc<dyn CurationRuleRepository>,
+    table_created: OnceCell<()>,
+}
+
+impl DatabaseCurationRuleStore {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        Ok(Self { repository: di_container.get(CURATION_RULE_REPOSITORY_DEP)?, table_created: OnceCell::new() })
+    }
+}
+
+#[async_trait]
+impl CurationRuleStore for DatabaseCurationRuleStore {
+    async fn load(&self) -> anyhow::Result<Vec<CurationRule>> {
+        // Retried on the next access when the database is unavailable
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.find_all().await
+    }
+
+    async fn save(&self, rule: &CurationRule) -> anyhow::Result<()> {
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.upsert(rule).await
+    }
+
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.delete(id).await
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::OnceCell;
+
+use crate::infrastructure::di_container::{DIContainer, SEARCH_EVENT_REPOSITORY_DEP};
+use crate::models::query_report::QueryCount;
+use crate::models::search_event::SearchEvent;
+use crate::repositories::search_event_repository_impl::SearchEventRepository;
+use crate::services::search_event_sink::SearchEventSink;
+
+pub struct DatabaseSearchEventSink {
+    repository: Arc<dyn SearchEventRepository>,
+    table_created: OnceCell<()>,
+}
+
+impl DatabaseSearchEventSink {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        Ok(Self { repository: di_container.get(SEARCH_EVENT_REPOSITORY_DEP)?, table_created: OnceCell::new() })
+    }
+}
+
+#[async_trait]
+impl SearchEventSink for DatabaseSearchEventSink {
+    async fn write(&self, events: &[SearchEvent]) -> anyhow::Result<()> {
+        // Retried on the next write when the database is unavailable
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.insert_events(events).await
+    }
+
+    async fn top_queries(&self, since: DateTime<Utc>, zero_hits_only: bool, limit: usize) -> anyhow::Result<Vec<QueryCount>> {
+        self.table_created.get_or_try_init(|| self.repository.create_table()).await?;
+        self.repository.find_top_queries(since, zero_hits_only, limit).await
+    }
+}
@@ -0,0 +1,70 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use axum::async_trait;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::models::blocked_id::BlockedId;
+use crate::services::blocklist_store::BlocklistStore;
+
+/// Blocked ids kept as a JSON array. Writes replace the file atomically.
+pub struct FileBlocklistStore {
+    path: PathBuf,
+    writing: Mutex<()>,
+}
+
+impl FileBlocklistStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, writing: Mutex::new(()) }
+    }
+
+    async fn write(&self, blocked: &[BlockedId]) -> anyhow::Result<()> {
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec_pretty(blocked)?).await
+            .with_context(|| format!("failed writing {}", temp_path.display()))?;
+        fs::rename(&temp_path, &self.path).await
+            .with_context(|| format!("failed replacing {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BlocklistStore for FileBlocklistStore {
+    async fn load(&self) -> anyhow::Result<Vec<BlockedId>> {
+        let content = match fs::read(&self.path).await {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(anyhow::Error::from(err).context(format!("failed reading {}", self.path.display()))),
+        };
+        serde_json::from_slice(&content).with_context(|| format!("failed parsing blocklist {}", self.path.display()))
+    }
+
+    async fn add(&self, blocked: &[BlockedId]) -> anyhow::Result<()> {
+        let _writing = self.writing.lock().await;
+        let mut current = self.load().await?;
+        let before = current.len();
+        for blocked in blocked {
+            if !current.iter().any(|current| current.entity() == blocked.entity() && current.id() == blocked.id()) {
+                current.push(blocked.clone());
+            }
+        }
+        if current.len() == before {
+            return Ok(());
+        }
+        self.write(&current).await
+    }
+
+    async fn remove(&self, entity: &str, id: u64) -> anyhow::Result<bool> {
+        let _writing = self.writing.lock().await;
+        let mut current = self.load().await?;
+        let before = current.len();
+        current.retain(|blocked| blocked.entity() != entity || blocked.id() != id);
+        if current.len() == before {
+            return Ok(false);
+        }
+        self.write(&current).await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use axum::async_trait;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::models::curation_rule::CurationRule;
+use crate::services::curation_rule_store::CurationRuleStore;
+
+/// Rules kept as a JSON array, which editors may also change by hand. Writes replace the file atomically.
+pub struct FileCurationRuleStore {
+    path: PathBuf,
+    writing: Mutex<()>,
+}
+
+impl FileCurationRuleStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, writing: Mutex::new(()) }
+    }
+
+    async fn write(&self, rules: &[CurationRule]) -> anyhow::Result<()> {
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec_pretty(rules)?).await
+            .with_context(|| format!("failed writing {}", temp_path.display()))?;
+        fs::rename(&temp_path, &self.path).await
+            .with_context(|| format!("failed replacing {}", self.path.display()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CurationRuleStore for FileCurationRuleStore {
+    async fn load(&self) -> anyhow::Result<Vec<CurationRule>> {
+        let content = match fs::read(&self.path).await {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(anyhow::Error::from(err).context(format!("failed reading {}", self.path.display()))),
+        };
+        serde_json::from_slice(&content).with_context(|| format!("failed parsing curation rules {}", self.path.display()))
+    }
+
+    async fn save(&self, rule: &CurationRule) -> anyhow::Result<()> {
+        let _writing = self.writing.lock().await;
+        let mut rules = self.load().await?;
+        match rules.iter_mut().find(|current| current.id() == rule.id()) {
+            Some(current) => *current = rule.clone(),
+            None => rules.push(rule.clone()),
+        }
+        self.write(&rules).await
+    }
+
+    async fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        let _writing = self.writing.lock().await;
+        let mut rules = self.load().await?;
+        let before = rules.len();
+        rules.retain(|rule| rule.id() != id);
+        if rules.len() == before {
+            return Ok(false);
+        }
+        self.write(&rules).await?;
+        Ok(true)
+    }
+}
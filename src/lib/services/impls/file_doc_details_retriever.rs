@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Context};
+use axum::async_trait;
+use tokio::fs;
+
+use crate::config::source_config::FileFormat;
+use crate::models::doc_details::DocDetails;
+use crate::models::source_document::SourceDocument;
+use crate::services::doc_details_retriever::DocDetailsRetriever;
+use crate::services::impls::in_memory_doc_details_retriever::InMemoryDocDetailsRetriever;
+
+pub struct FileDocDetailsRetriever {
+    path: PathBuf,
+    format: FileFormat,
+    documents: RwLock<Vec<SourceDocument>>,
+}
+
+impl FileDocDetailsRetriever {
+    pub fn new(path: PathBuf, format: FileFormat) -> Self {
+        Self { path, format, documents: RwLock::new(Vec::new()) }
+    }
+
+    async fn load(&self) -> anyhow::Result<Vec<SourceDocument>> {
+        let content = fs::read_to_string(&self.path).await
+            .with_context(|| format!("failed reading data source {}", self.path.display()))?;
+
+        match self.format {
+            FileFormat::Jsonl => content.lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(number, line)| serde_json::from_str::<SourceDocument>(line)
+                    .with_context(|| format!("invalid document at {}:{}", self.path.display(), number + 1)))
+                .collect(),
+            FileFormat::Csv => csv::Reader::from_reader(content.as_bytes())
+                .deserialize::<SourceDocument>()
+                .map(|record| record.with_context(|| format!("invalid document in {}", self.path.display())))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl DocDetailsRetriever for FileDocDetailsRetriever {
+    async fn retrieve(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<DocDetails>> {
+        // Every language build starts at offset 0, reloading there picks up file changes on each reindex
+        if offset == 0 {
+            let documents = self.load().await?;
+            *self.documents.write().map_err(|_| anyhow!("data source cache poisoned"))? = documents;
+        }
+
+        let documents = self.documents.read().map_err(|_| anyhow!("data source cache poisoned"))?;
+        Ok(InMemoryDocDetailsRetriever::page(&documents, lang, limit, offset))
+    }
+
+    async fn retrieve_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<DocDetails>> {
+        let documents = self.load().await?;
+        Ok(InMemoryDocDetailsRetriever::with_ids(&documents, lang, ids))
+    }
+}
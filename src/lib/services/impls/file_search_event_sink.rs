@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::task;
+
+use crate::models::query_report::QueryCount;
+use crate::models::search_event::SearchEvent;
+use crate::services::search_event_sink::SearchEventSink;
+
+/// Appends events as JSON lines, rotating the file once it reaches `max_bytes`.
+pub struct FileSearchEventSink {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl FileSearchEventSink {
+    pub fn new(path: PathBuf, max_bytes: u64, max_files: usize) -> Self {
+        Self { inner: Arc::new(Inner { path, max_bytes, max_files }) }
+    }
+}
+
+impl Inner {
+    fn write(&self, events: &[SearchEvent]) -> anyhow::Result<()> {
+        let mut buffer = Vec::new();
+        for event in events {
+            serde_json::to_writer(&mut buffer, event)?;
+            buffer.push(b'\n');
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)
+            .with_context(|| format!("failed opening {}", self.path.display()))?;
+        file.write_all(&buffer)?;
+        if file.metadata()?.len() >= self.max_bytes {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    /// Shifts `<path>.N` to `<path>.N+1`, dropping the oldest, and moves the current file to `<path>.1`.
+    fn rotate(&self) -> anyhow::Result<()> {
+        for index in (1..self.max_files).rev() {
+            match fs::rename(self.rotated(index), self.rotated(index + 1)) {
+                Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+        fs::rename(&self.path, self.rotated(1))?;
+        Ok(())
+    }
+
+    fn rotated(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        PathBuf::from(path)
+    }
+
+    fn top_queries(&self, since: DateTime<Utc>, zero_hits_only: bool, limit: usize) -> anyhow::Result<Vec<QueryCount>> {
+        let mut counts: HashMap<(String, String, String), u64> = HashMap::new();
+        let files = std::iter::once(self.path.clone()).chain((1..=self.max_files).map(|index| self.rotated(index)));
+        for path in files {
+            for event in read_events(&path)? {
+                if event.timestamp() < since || (zero_hits_only && event.hits() > 0) {
+                    continue;
+                }
+                let key = (event.query().to_string(), event.entity().to_string(), event.language().to_string());
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+
+        let mut result = counts.into_iter()
+            .map(|((query, entity, language), count)| QueryCount::new(query, entity, language, count))
+            .collect::<Vec<_>>();
+        result.sort_by(|left, right| right.count().cmp(&left.count()).then_with(|| left.query().cmp(right.query())));
+        result.truncate(limit);
+        Ok(result)
+    }
+}
+
+/// Events of a JSONL file, skipping lines that can't be parsed, e.g. one cut short by a crash.
+fn read_events(path: &Path) -> anyhow::Result<Vec<SearchEvent>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut events = Vec::new();
+    for line in BufReader::new(file).lines() {
+        match serde_json::from_str::<SearchEvent>(&line?) {
+            Ok(event) => events.push(event),
+            Err(err) => log::debug!("skipping malformed search event in {}: {err}", path.display()),
+        }
+    }
+    Ok(events)
+}
+
+#[async_trait]
+impl SearchEventSink for FileSearchEventSink {
+    async fn write(&self, events: &[SearchEvent]) -> anyhow::Result<()> {
+        let inner = self.inner.clone();
+        let events = events.to_vec();
+        task::spawn_blocking(move || inner.write(&events)).await?
+    }
+
+    async fn top_queries(&self, since: DateTime<Utc>, zero_hits_only: bool, limit: usize) -> anyhow::Result<Vec<QueryCount>> {
+        let inner = self.inner.clone();
+        task::spawn_blocking(move || inner.top_queries(since, zero_hits_only, limit)).await?
+    }
+}
@@ -0,0 +1,44 @@
+use axum::async_trait;
+
+use crate::models::doc_details::DocDetails;
+use crate::models::source_document::SourceDocument;
+use crate::services::doc_details_retriever::DocDetailsRetriever;
+
+pub struct InMemoryDocDetailsRetriever {
+    documents: Vec<SourceDocument>,
+}
+
+impl InMemoryDocDetailsRetriever {
+    pub fn new(documents: Vec<SourceDocument>) -> Self {
+        Self { documents }
+    }
+
+    /// Page of the documents in `lang`, in source order.
+    pub(crate) fn page(documents: &[SourceDocument], lang: &str, limit: u64, offset: u64) -> Vec<DocDetails> {
+        documents.iter()
+            .filter(|document| document.language().eq_ignore_ascii_case(lang))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|document| DocDetails::new(document.id(), document.title().to_string()))
+            .collect()
+    }
+
+    /// Documents in `lang` with one of `ids`.
+    pub(crate) fn with_ids(documents: &[SourceDocument], lang: &str, ids: &[u64]) -> Vec<DocDetails> {
+        documents.iter()
+            .filter(|document| document.language().eq_ignore_ascii_case(lang) && ids.contains(&document.id()))
+            .map(|document| DocDetails::new(document.id(), document.title().to_string()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl DocDetailsRetriever for InMemoryDocDetailsRetriever {
+    async fn retrieve(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<DocDetails>> {
+        Ok(Self::page(&self.documents, lang, limit, offset))
+    }
+
+    async fn retrieve_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<DocDetails>> {
+        Ok(Self::with_ids(&self.documents, lang, ids))
+    }
+}
@@ -1,29 +1,57 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use dashmap::DashMap;
+use anyhow::{anyhow, Context};
+use dashmap::{DashMap, DashSet};
 use dashmap::mapref::one::{Ref, RefMut};
-use tantivy::{doc, DocAddress, Index, IndexBuilder, IndexReader, IndexWriter as TantivyIndexWriter, Score, TantivyDocument};
-use tantivy::collector::TopDocs;
-use tantivy::query::{BooleanQuery, Occur, Query, RegexQuery};
-use tantivy::schema::{Field, OwnedValue, Schema, STORED, TEXT};
+use tantivy::{doc, DocAddress, DocId, Index, IndexBuilder, IndexReader, IndexWriter as TantivyIndexWriter, Score, SegmentReader, TantivyDocument, Term};
+use tantivy::collector::{Count, TopDocs};
+use tantivy::indexer::{LogMergePolicy, MergePolicy, NoMergePolicy};
+use tantivy::query::{BooleanQuery, Occur, Query, RegexQuery, TermQuery};
+use tantivy::schema::{FAST, Field, INDEXED, IndexRecordOption, OwnedValue, Schema, STORED, TEXT};
 
+use crate::config::index_writer_config::{IndexWriterConfig, MergePolicyConfig};
 use crate::models::doc_details::DocDetails;
+use crate::models::entity::Entity;
 use crate::models::language::Language;
+use crate::models::scored_doc::ScoredDoc;
+use crate::models::search_error::SearchError;
+use crate::services::index_snapshot;
 
 const TITLE_FIELD: &str = "title";
 const ID_FIELD: &str = "id";
-const LIMIT_RESULT_SIZE: usize = 75;
-const MEMORY_BUDGET_BYTES: usize = 100_000_000;
+/// Maximum hits returned by a single search.
+pub const LIMIT_RESULT_SIZE: usize = 75;
 
 // Structs
 pub struct IndexProcessor {
     inner: DashMap<Language, Inner>,
+    last_builds: DashMap<Language, SystemTime>,
+    generations: DashMap<Language, u64>,
+    restored: DashSet<Language>,
+    persistence: Option<(Entity, PathBuf)>,
+    writer_config: IndexWriterConfig,
+    max_snapshot_bytes: u64,
+    listeners: RwLock<Vec<ChangeListener>>,
+    /// Live writes of every language being rebuilt, replayed on the new index once built.
+    journals: DashMap<Language, Option<Vec<LiveWrite>>>,
+}
+
+type ChangeListener = Box<dyn Fn(Language) + Send + Sync>;
+
+/// Write applied to a live index between builds.
+enum LiveWrite {
+    Upsert(Vec<DocDetails>),
+    Delete(Vec<u64>),
 }
 
 struct Inner {
-    pub index_writer: Mutex<TantivyIndexWriter>,
+    pub index: Index,
+    /// Serializes the writers, Tantivy allows a single one per index.
+    pub write_lock: Mutex<()>,
     pub index_reader: IndexReader,
     pub fields: HashMap<String, Field>,
 }
@@ -33,12 +61,20 @@ struct Inner {
 impl Inner {
     fn new() -> anyhow::Result<Inner> {
         let mut schema_builder = Schema::builder();
-        let title = schema_builder.add_text_field(TITLE_FIELD, TEXT);
-        let id = schema_builder.add_u64_field(ID_FIELD, STORED);
+        schema_builder.add_text_field(TITLE_FIELD, TEXT | STORED);
+        // Indexed so documents can be deleted by id, fast so hits can be boosted by id while scoring
+        schema_builder.add_u64_field(ID_FIELD, STORED | INDEXED | FAST);
         let schema = schema_builder.build();
 
-        let index = IndexBuilder::create_in_ram(Index::builder().schema(schema.clone()))?;
-        let index_writer = index.writer(MEMORY_BUDGET_BYTES)?;
+        let index = IndexBuilder::create_in_ram(Index::builder().schema(schema))?;
+        Self::open(index)
+    }
+
+    fn open(index: Index) -> anyhow::Result<Inner> {
+        let schema = index.schema();
+        let title = schema.get_field(TITLE_FIELD)?;
+        let id = schema.get_field(ID_FIELD)?;
+
         let index_reader = index.reader()?;
 
         let mut fields = HashMap::new();
@@ -46,31 +82,83 @@ impl Inner {
         fields.insert(ID_FIELD.to_string(), id);
 
         Ok(Inner {
-            index_writer: Mutex::new(index_writer),
+            index,
+            write_lock: Mutex::new(()),
             index_reader,
             fields,
         })
     }
-    fn write_all(&self, data: &[DocDetails]) -> anyhow::Result<()> {
+
+    /// Runs `operations` with a writer created for the occasion and commits them. With `force_merge`,
+    /// the committed segments are then merged into one. The writer is dropped once its merges are done.
+    fn write<F>(&self, config: &IndexWriterConfig, force_merge: bool, operations: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&mut TantivyIndexWriter) -> anyhow::Result<()>,
+    {
+        let _write_lock = self.write_lock.lock().map_err(|_| anyhow!("index writer poisoned"))?;
+        let mut writer: TantivyIndexWriter = match config.threads() {
+            Some(threads) => self.index.writer_with_num_threads(threads, config.memory_budget_bytes())?,
+            None => self.index.writer(config.memory_budget_bytes())?,
+        };
+        writer.set_merge_policy(merge_policy(config.merge_policy()));
+
+        operations(&mut writer)?;
+        writer.commit()?;
+        if force_merge {
+            let segment_ids = self.index.searchable_segment_ids()?;
+            if segment_ids.len() > 1 {
+                writer.merge(&segment_ids).wait()?;
+            }
+        }
+        writer.wait_merging_threads()?;
+
+        self.index_reader.reload()?;
+        Ok(())
+    }
+
+    /// Adds `data`, replacing the indexed documents with the same ids. See [`latest_by_id`] for duplicated ids.
+    /// With `force_merge`, the index is merged into a single segment afterwards if its config allows it.
+    fn write_all(&self, data: &[DocDetails], config: &IndexWriterConfig, force_merge: bool) -> anyhow::Result<()> {
         let id_field = self.id();
         let title_field = self.title();
-        if let Ok(mut writer) = self.index_writer.lock() {
-            for doc in data.iter() {
+        let latest = latest_by_id(data);
+        if latest.len() < data.len() {
+            tracing::info!(duplicates = data.len() - latest.len(), "collapsed documents sharing an id");
+        }
+
+        self.write(config, force_merge && config.force_merge(), |writer| {
+            for doc in latest {
+                writer.delete_term(Term::from_field_u64(id_field, doc.id()));
                 writer.add_document(doc!(
             title_field => doc.title(),
             id_field => doc.id()))?;
             }
-            writer.commit()?;
-        }
+            Ok(())
+        })
+    }
 
-        self.index_reader.reload()?;
-        Ok(())
+    fn search(&self, tokens: &[&str], boosts: &HashMap<u64, f32>) -> Result<Vec<u64>, SearchError> {
+        Ok(self.search_scored(tokens, boosts)?.iter().map(ScoredDoc::id).collect())
+    }
+
+    fn contains(&self, id: u64) -> Result<bool, SearchError> {
+        let query = TermQuery::new(Term::from_field_u64(self.id(), id), IndexRecordOption::Basic);
+        // Counted rather than looked up in the term dictionary, which still holds deleted documents
+        let count = self.index_reader.searcher().search(&query, &Count).map_err(anyhow::Error::from)?;
+        Ok(count > 0)
     }
 
-    fn search(&self, tokens: &[&str]) -> anyhow::Result<Vec<u64>> {
+    /// Hits of `tokens` best scored first, the score of every hit found in `boosts` being multiplied
+    /// by its boost while collecting, so boosted hits compete with the whole index for the top.
+    #[tracing::instrument(level = "debug", skip_all, fields(tokens = tokens.len(), boosts = boosts.len()))]
+    fn search_scored(&self, tokens: &[&str], boosts: &HashMap<u64, f32>) -> Result<Vec<ScoredDoc>, SearchError> {
         let title = self.title();
         let id = self.id();
 
+        if tokens.is_empty() {
+            return Err(SearchError::EmptyQuery);
+        }
+
         let last_index = tokens.len() - 1;
         let mut subqueries = Vec::new();
         for (current_index, token) in tokens.iter().enumerate() {
@@ -79,33 +167,67 @@ impl Inner {
                 current.to_mut().push_str(".*");
             }
 
-            subqueries.push((Occur::Must, Box::new(RegexQuery::from_pattern(current.as_ref(), title)?)
-                as Box<dyn Query>));
+            let regex_query = RegexQuery::from_pattern(current.as_ref(), title)
+                .map_err(|err| SearchError::InvalidQuery(err.to_string()))?;
+            subqueries.push((Occur::Must, Box::new(regex_query) as Box<dyn Query>));
         }
 
         let query = BooleanQuery::new(subqueries);
         let searcher = self.index_reader.searcher();
 
         let mut result = Vec::new();
-        let top_docs: Vec<(Score, DocAddress)> = searcher.search(&query, &TopDocs::with_limit(LIMIT_RESULT_SIZE))?;
-        for (_score, doc_address) in top_docs {
-            let retrieved_doc = searcher.doc::<TantivyDocument>(doc_address)?;
-
-            if let Some(owned_value) = retrieved_doc.get_first(id) {
-                match owned_value {
-                    OwnedValue::U64(value) => {
-                        result.push(*value)
-                    }
-                    _ => {
-                        continue;
-                    }
+        let top_docs = TopDocs::with_limit(LIMIT_RESULT_SIZE);
+        let top_docs: Vec<(Score, DocAddress)> = if boosts.is_empty() {
+            searcher.search(&query, &top_docs)
+        } else {
+            // Segment scorers may not borrow from the search call
+            let boosts = Arc::new(boosts.clone());
+            searcher.search(&query, &top_docs.tweak_score(move |segment_reader: &SegmentReader| {
+                let ids = segment_reader.fast_fields().u64(ID_FIELD).ok();
+                let boosts = boosts.clone();
+                move |doc: DocId, score: Score| {
+                    let boost = ids.as_ref()
+                        .and_then(|ids| ids.first(doc))
+                        .and_then(|id| boosts.get(&id))
+                        .copied()
+                        .unwrap_or(1.0);
+                    score * boost
                 }
+            }))
+        }.map_err(anyhow::Error::from)?;
+        for (score, doc_address) in top_docs {
+            let retrieved_doc = searcher.doc::<TantivyDocument>(doc_address)
+                .map_err(anyhow::Error::from)?;
+
+            if let Some(OwnedValue::U64(value)) = retrieved_doc.get_first(id) {
+                let doc_title = match retrieved_doc.get_first(title) {
+                    Some(OwnedValue::Str(doc_title)) => doc_title.clone(),
+                    _ => String::new(),
+                };
+                result.push(ScoredDoc::new(*value, doc_title, score));
             }
         }
 
+        let mut seen = HashSet::new();
+        result.retain(|doc| seen.insert(doc.id()));
         Ok(result)
     }
 
+    /// Deletes every document with one of `ids`.
+    fn delete_ids(&self, ids: &[u64], config: &IndexWriterConfig) -> anyhow::Result<()> {
+        let id_field = self.id();
+        self.write(config, false, |writer| {
+            for id in ids {
+                writer.delete_term(Term::from_field_u64(id_field, *id));
+            }
+            Ok(())
+        })
+    }
+
+    fn segment_count(&self) -> usize {
+        self.index_reader.searcher().segment_readers().len()
+    }
+
     fn title(&self) -> Field {
         *self.fields.get(TITLE_FIELD).unwrap()
     }
@@ -114,6 +236,44 @@ impl Inner {
         *self.fields.get(ID_FIELD).unwrap()
     }
 }
+
+impl LiveWrite {
+    /// Small writes between builds use a small writer of their own and are never force merged.
+    fn apply(&self, inner: &Inner) -> anyhow::Result<()> {
+        let config = IndexWriterConfig::live();
+        match self {
+            LiveWrite::Upsert(data) => inner.write_all(data, &config, false),
+            LiveWrite::Delete(ids) => inner.delete_ids(ids, &config),
+        }
+    }
+}
+
+fn merge_policy(config: &MergePolicyConfig) -> Box<dyn MergePolicy> {
+    match config {
+        MergePolicyConfig::Log { min_num_segments, max_docs_before_merge } => {
+            let mut policy = LogMergePolicy::default();
+            if let Some(min_num_segments) = min_num_segments {
+                policy.set_min_num_segments(*min_num_segments);
+            }
+            if let Some(max_docs_before_merge) = max_docs_before_merge {
+                policy.set_max_docs_before_merge(*max_docs_before_merge);
+            }
+            Box::new(policy)
+        }
+        MergePolicyConfig::None => Box::new(NoMergePolicy),
+    }
+}
+
+/// Documents of `data` keeping the last one of every id, in their `data` order. Data sources list
+/// their documents in a stable order, making the winner of duplicated ids deterministic.
+fn latest_by_id(data: &[DocDetails]) -> Vec<&DocDetails> {
+    let last_positions: HashMap<u64, usize> = data.iter().enumerate().map(|(position, doc)| (doc.id(), position)).collect();
+    data.iter().enumerate()
+        .filter(|(position, doc)| last_positions.get(&doc.id()) == Some(position))
+        .map(|(_, doc)| doc)
+        .collect()
+}
+
 impl IndexProcessor {
     pub fn new() -> anyhow::Result<Self> {
         let indexers = DashMap::new();
@@ -122,10 +282,163 @@ impl IndexProcessor {
         }
 
         Ok(Self {
-            inner: indexers
+            inner: indexers,
+            last_builds: DashMap::new(),
+            generations: DashMap::new(),
+            restored: DashSet::new(),
+            persistence: None,
+            writer_config: IndexWriterConfig::default(),
+            max_snapshot_bytes: index_snapshot::DEFAULT_MAX_SNAPSHOT_BYTES,
+            listeners: RwLock::new(Vec::new()),
+            journals: Language::all().into_iter().map(|lang| (lang, None)).collect(),
         })
     }
 
+    /// Processor keeping a snapshot of every successful build in `dir`, so it survives restarts.
+    pub fn with_persistence(entity: Entity, dir: PathBuf) -> anyhow::Result<Self> {
+        let mut processor = Self::new()?;
+        processor.persistence = Some((entity, dir));
+        Ok(processor)
+    }
+
+    /// Same processor writing its indexes with the `writer_config` settings.
+    pub fn with_writer_config(mut self, writer_config: IndexWriterConfig) -> Self {
+        self.writer_config = writer_config;
+        self
+    }
+
+    /// Same processor rejecting snapshots unpacking to more than `max_snapshot_bytes`.
+    pub fn with_max_snapshot_bytes(mut self, max_snapshot_bytes: u64) -> Self {
+        self.max_snapshot_bytes = max_snapshot_bytes;
+        self
+    }
+
+    /// Loads the persisted index of every language not built yet, returns the restored languages.
+    pub fn restore(&self) -> Vec<Language> {
+        let Some((entity, dir)) = &self.persistence else {
+            return Vec::new();
+        };
+
+        let mut restored = Vec::new();
+        for language in Language::all() {
+            if self.last_build(language).is_some() || !index_snapshot::snapshot_path(dir, *entity, language).exists() {
+                continue;
+            }
+            match self.import_snapshot(*entity, language, dir) {
+                Ok(()) => restored.push(language),
+                Err(err) => log::warn!("failed restoring persisted {} {} index: {err:?}", <&str>::from(*entity), <&str>::from(language)),
+            }
+        }
+        restored
+    }
+
+    /// Registers a callback invoked with the language of every index swap, write or snapshot import.
+    pub fn on_change(&self, listener: impl Fn(Language) + Send + Sync + 'static) {
+        if let Ok(mut listeners) = self.listeners.write() {
+            listeners.push(Box::new(listener));
+        }
+    }
+
+    /// Whether the live index was loaded from disk and not rebuilt from the database since.
+    pub fn is_stale(&self, language: Language) -> bool {
+        self.restored.contains(&language)
+    }
+
+    /// Time of the last successful write into the index of the given language, `None` if never built.
+    pub fn last_build(&self, language: Language) -> Option<SystemTime> {
+        self.last_builds.get(&language).map(|entry| *entry)
+    }
+
+    /// Same search as [`IndexSearcher::search`] but keeping stored titles and scores, for relevance debugging.
+    pub fn explain(&self, language: Language, tokens: &[&str]) -> Result<Vec<ScoredDoc>, SearchError> {
+        self.inner(&language).search_scored(tokens, &HashMap::new())
+    }
+
+    /// Removes the documents with one of `ids` from the index of every language right away, without
+    /// waiting for the next build. Persisted snapshots are rewritten, so a restart never restores them.
+    pub fn delete_ids(&self, ids: &[u64]) -> anyhow::Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        for language in Language::all() {
+            self.apply(language, LiveWrite::Delete(ids.to_vec()))?;
+            if self.last_build(language).is_some() {
+                self.persist(language)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn segment_count(&self, language: Language) -> usize {
+        self.inner(&language).segment_count()
+    }
+
+    pub fn export_snapshot(&self, entity: Entity, language: Language, dir: &Path) -> anyhow::Result<PathBuf> {
+        let built_at = self.last_build(language).ok_or_else(|| anyhow!("index was never built"))?;
+        let inner = self.inner(&language);
+        // Holding the write lock keeps commits and their file garbage collection away while copying
+        let _write_lock = inner.write_lock.lock().map_err(|_| anyhow!("index writer poisoned"))?;
+        index_snapshot::export(&inner.index, entity, language, built_at, dir)
+    }
+
+    pub fn import_snapshot(&self, entity: Entity, language: Language, dir: &Path) -> anyhow::Result<()> {
+        let (index, manifest) = index_snapshot::import(&index_snapshot::snapshot_path(dir, entity, language), entity, language, self.max_snapshot_bytes)?;
+        *self.inner_mut(&language) = Inner::open(index)?;
+        self.last_builds.insert(language, index_snapshot::created_at(&manifest));
+        self.restored.insert(language);
+        self.next_generation(language);
+        Ok(())
+    }
+
+    /// Applies `write` to the live index of `language`, journaling it while the index is rebuilt.
+    fn apply(&self, language: Language, write: LiveWrite) -> anyhow::Result<()> {
+        // Held until journaled, so the write is either swapped away with the old index after being replayed or
+        // applied after the swap
+        let mut journal = self.journals.get_mut(&language).unwrap();
+        write.apply(&self.inner(&language))?;
+        if let Some(journal) = journal.as_mut() {
+            journal.push(write);
+        }
+        drop(journal);
+        self.next_generation(language);
+        Ok(())
+    }
+
+    /// Moves to a new generation and notifies the change listeners. Generations are seeded from the wall
+    /// clock, so they keep increasing across restarts and cached responses of a previous process are never
+    /// mistaken for the current index.
+    fn next_generation(&self, language: Language) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_millis() as u64).unwrap_or_default();
+        {
+            let mut generation = self.generations.entry(language).or_insert(0);
+            *generation = now.max(*generation + 1);
+        }
+
+        if let Ok(listeners) = self.listeners.read() {
+            listeners.iter().for_each(|listener| listener(language));
+        }
+    }
+
+    fn built(&self, language: Language) {
+        self.last_builds.insert(language, SystemTime::now());
+        self.next_generation(language);
+        self.restored.remove(&language);
+
+        // A failed persistence only costs the next cold start, the live index is already swapped
+        if let Err(err) = self.persist(language) {
+            log::warn!("{err:?}");
+        }
+    }
+
+    /// Exports the index of `language` to the persistence directory, if any.
+    fn persist(&self, language: Language) -> anyhow::Result<()> {
+        if let Some((entity, dir)) = &self.persistence {
+            self.export_snapshot(*entity, language, dir)
+                .with_context(|| format!("failed persisting {} {} index", <&str>::from(*entity), <&str>::from(language)))?;
+        }
+        Ok(())
+    }
+
     fn inner<'a>(&'a self, language: &'a Language) -> Ref<'a, Language, Inner> {
         self.inner.get(language).unwrap()
     }
@@ -141,31 +454,95 @@ impl IndexProcessor {
 pub trait IndexWriter {
     fn write_all(&self, lang: Language, data: &[DocDetails]) -> anyhow::Result<()>;
 
+    /// Starts journaling the live writes of `lang` before its data source is read for a rebuild, so
+    /// [`IndexWriter::swap_index`] replays them on the new index instead of swapping them away.
+    fn begin_build(&self, lang: Language);
+
+    /// Stops journaling the live writes of `lang`, the rebuild being abandoned.
+    fn cancel_build(&self, lang: Language);
+
     fn swap_index(&self, lang: Language, data: &[DocDetails]) -> anyhow::Result<()>;
+
+    /// Writes `data` into the live index of `lang`, replacing the documents with the same ids, and
+    /// makes them searchable right away. The next build replaces them with its data source content.
+    fn upsert(&self, lang: Language, data: &[DocDetails]) -> anyhow::Result<()>;
+
+    /// Deletes the documents with one of `ids` from the live index of `lang`.
+    fn delete(&self, lang: Language, ids: &[u64]) -> anyhow::Result<()>;
 }
 
 pub trait IndexSearcher {
-    fn search(&self, lang: Language, tokens: &[&str]) -> anyhow::Result<Vec<u64>>;
+    fn search(&self, lang: Language, tokens: &[&str]) -> Result<Vec<u64>, SearchError>;
+
+    /// Same as [`IndexSearcher::search`], multiplying the score of the hits found in `boosts`.
+    fn search_boosted(&self, lang: Language, tokens: &[&str], boosts: &HashMap<u64, f32>) -> Result<Vec<u64>, SearchError>;
+
+    /// Identifier of the live index content of `lang`, changing whenever the index is written or swapped.
+    fn generation(&self, lang: Language) -> u64;
+
+    /// Whether the live index of `lang` holds a document with `id`.
+    fn contains(&self, lang: Language, id: u64) -> Result<bool, SearchError>;
 }
 
 impl IndexSearcher for IndexProcessor {
-    fn search(&self, lang: Language, tokens: &[&str]) -> anyhow::Result<Vec<u64>> {
+    fn search(&self, lang: Language, tokens: &[&str]) -> Result<Vec<u64>, SearchError> {
+        self.search_boosted(lang, tokens, &HashMap::new())
+    }
+
+    fn search_boosted(&self, lang: Language, tokens: &[&str], boosts: &HashMap<u64, f32>) -> Result<Vec<u64>, SearchError> {
         let inner = self.inner(&lang);
-        inner.search(tokens)
+        inner.search(tokens, boosts)
+    }
+
+    fn generation(&self, lang: Language) -> u64 {
+        self.generations.get(&lang).map(|generation| *generation).unwrap_or_default()
+    }
+
+    fn contains(&self, lang: Language, id: u64) -> Result<bool, SearchError> {
+        self.inner(&lang).contains(id)
     }
 }
 
 impl IndexWriter for IndexProcessor {
     fn write_all(&self, lang: Language, data: &[DocDetails]) -> anyhow::Result<()> {
-        let inner = self.inner(&lang);
-        inner.write_all(data)
+        self.inner(&lang).write_all(data, &self.writer_config, true)?;
+        self.built(lang);
+        Ok(())
+    }
+
+    fn begin_build(&self, lang: Language) {
+        *self.journals.get_mut(&lang).unwrap() = Some(Vec::new());
+    }
+
+    fn cancel_build(&self, lang: Language) {
+        *self.journals.get_mut(&lang).unwrap() = None;
     }
 
     fn swap_index(&self, lang: Language, data: &[DocDetails]) -> anyhow::Result<()> {
         let inner = Inner::new()?;
-        inner.write_all(data)?;
+        inner.write_all(data, &self.writer_config, true)?;
 
+        // Live writes wait for the swap, none of them is applied to the old index only
+        let mut journal = self.journals.get_mut(&lang).unwrap();
+        let writes = journal.take().unwrap_or_default();
+        if !writes.is_empty() {
+            log::info!("replaying {} live writes applied during the {} build", writes.len(), <&str>::from(lang));
+        }
+        for write in &writes {
+            write.apply(&inner)?;
+        }
         *self.inner_mut(&lang) = inner;
+        drop(journal);
+
+        self.built(lang);
         Ok(())
     }
+
+    fn upsert(&self, lang: Language, data: &[DocDetails]) -> anyhow::Result<()> {
+        self.apply(lang, LiveWrite::Upsert(data.to_vec()))
+    }
+
+    fn delete(&self, lang: Language, ids: &[u64]) -> anyhow::Result<()> {
+        self.apply(lang, LiveWrite::Delete(ids.to_vec()))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,102 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::bail;
+use chrono::{DateTime, Timelike, Utc};
+use cron::Schedule;
+use rand::Rng;
+
+use crate::config::indexer_runner_config::{BlackoutConfig, ScheduleConfig};
+
+const MAX_CRON_LOOKAHEAD: usize = 10_000;
+
+pub struct IndexSchedule {
+    kind: ScheduleKind,
+    jitter: u64,
+    blackout: Option<BlackoutConfig>,
+}
+
+enum ScheduleKind {
+    Cron(Box<Schedule>),
+    Interval(chrono::Duration),
+}
+
+impl IndexSchedule {
+    pub fn new(config: &ScheduleConfig) -> anyhow::Result<Self> {
+        let kind = match (config.cron(), config.interval()) {
+            (Some(expression), _) => ScheduleKind::Cron(Box::new(Schedule::from_str(expression)?)),
+            (None, Some(interval)) if interval > 0 => ScheduleKind::Interval(chrono::Duration::seconds(interval as i64)),
+            _ => bail!("schedule requires a cron expression or an interval greater than zero"),
+        };
+
+        if let Some(blackout) = config.blackout() {
+            if blackout.start() > 23 || blackout.end() > 23 {
+                bail!("blackout hours must be in range 0..=23");
+            }
+        }
+
+        Ok(Self { kind, jitter: config.jitter(), blackout: config.blackout() })
+    }
+
+    /// Next run strictly after `from`, delayed by a random jitter, skipping any fire time that falls
+    /// inside the blackout window. A jitter reaching into the window is dropped, running on time instead.
+    pub fn next_run(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let candidate = self.next_fire_time(from)?;
+        let jittered = chrono::Duration::from_std(self.jitter()).ok()
+            .and_then(|jitter| candidate.checked_add_signed(jitter))
+            .unwrap_or(candidate);
+        if self.in_blackout(&jittered) {
+            return Some(candidate);
+        }
+        Some(jittered)
+    }
+
+    fn next_fire_time(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match &self.kind {
+            ScheduleKind::Cron(schedule) => {
+                schedule.after(&from)
+                    .take(MAX_CRON_LOOKAHEAD)
+                    .find(|candidate| !self.in_blackout(candidate))
+            }
+            ScheduleKind::Interval(interval) => {
+                let candidate = from + *interval;
+                if self.in_blackout(&candidate) {
+                    return Some(self.blackout_end(&candidate));
+                }
+                Some(candidate)
+            }
+        }
+    }
+
+    fn jitter(&self) -> Duration {
+        if self.jitter == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs(rand::thread_rng().gen_range(0..=self.jitter))
+    }
+
+    pub fn in_blackout(&self, at: &DateTime<Utc>) -> bool {
+        let Some(blackout) = self.blackout else {
+            return false;
+        };
+
+        let hour = at.hour();
+        if blackout.start() <= blackout.end() {
+            blackout.start() <= hour && hour < blackout.end()
+        } else {
+            hour >= blackout.start() || hour < blackout.end()
+        }
+    }
+
+    fn blackout_end(&self, at: &DateTime<Utc>) -> DateTime<Utc> {
+        let end_hour = self.blackout.map(|blackout| blackout.end()).unwrap_or_default();
+        let mut end = at.date_naive()
+            .and_hms_opt(end_hour, 0, 0)
+            .expect("Blackout hour validated")
+            .and_utc();
+        if end <= *at {
+            end += chrono::Duration::days(1);
+        }
+        end
+    }
+}
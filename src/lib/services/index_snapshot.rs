@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use sha2::{Digest, Sha256};
+use tantivy::directory::{Directory, RamDirectory};
+use tantivy::directory::error::OpenReadError;
+use tantivy::Index;
+
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::models::snapshot_manifest::{SnapshotFile, SnapshotManifest};
+
+/// 2: the archive checksum is stored in the manifest instead of a `.sha256` file next to the archive.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+/// Bumped whenever the fields of the index schema change, snapshots of other schema versions are rejected.
+/// 2: ids are indexed, so documents can be deleted and replaced by id.
+/// 3: ids are fast fields, so click feedback boosts every hit while scoring.
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 3;
+pub const SNAPSHOT_EXTENSION: &str = "snapshot.tar.gz";
+pub const DEFAULT_MAX_SNAPSHOT_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const INDEX_ENTRY_PREFIX: &str = "index/";
+const META_FILE: &str = "meta.json";
+
+pub fn snapshot_path(dir: &Path, entity: Entity, language: Language) -> PathBuf {
+    let entity: &str = entity.into();
+    let language: &str = language.into();
+    dir.join(format!("{}_{}.{SNAPSHOT_EXTENSION}", entity.to_ascii_lowercase(), language.to_ascii_lowercase()))
+}
+
+/// Writes the committed state of `index` as a gzipped tar archive, its checksums held by the manifest.
+/// The archive is written to a temporary file and renamed, so readers never observe a partial snapshot.
+pub fn export(index: &Index, entity: Entity, language: Language, created_at: SystemTime, dir: &Path) -> anyhow::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let mut file_names = vec![PathBuf::from(META_FILE)];
+    for segment_meta in index.searchable_segment_metas()? {
+        let mut segment_files = segment_meta.list_files().into_iter().collect::<Vec<_>>();
+        segment_files.sort();
+        file_names.extend(segment_files);
+    }
+
+    let mut files = Vec::new();
+    let mut contents = Vec::new();
+    for file_name in file_names {
+        // Raw reads keep the tantivy footers, `open_read` would strip them
+        let bytes = match index.directory().atomic_read(&file_name) {
+            Ok(bytes) => bytes,
+            // Segment components are optional (e.g. no deletes), only listed files that exist are shipped
+            Err(OpenReadError::FileDoesNotExist(_)) => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let name = file_name.to_string_lossy().to_string();
+        files.push(SnapshotFile::new(name.clone(), bytes.len() as u64, hex::encode(Sha256::digest(&bytes))));
+        contents.push((name, bytes));
+    }
+
+    let manifest = SnapshotManifest::new(
+        SNAPSHOT_FORMAT_VERSION,
+        SNAPSHOT_SCHEMA_VERSION,
+        <&str>::from(entity).to_string(),
+        <&str>::from(language).to_string(),
+        created_at.duration_since(UNIX_EPOCH)?.as_secs(),
+        index.reader()?.searcher().num_docs(),
+        files,
+    );
+
+    let path = snapshot_path(dir, entity, language);
+    let tmp_path = path.with_extension("tmp");
+    {
+        let encoder = GzEncoder::new(BufWriter::new(File::create(&tmp_path)?), Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+        append(&mut archive, MANIFEST_ENTRY, &serde_json::to_vec_pretty(&manifest)?)?;
+        for (name, bytes) in contents.iter() {
+            append(&mut archive, &format!("{INDEX_ENTRY_PREFIX}{name}"), bytes)?;
+        }
+        archive.into_inner()?.finish()?.flush()?;
+    }
+
+    fs::rename(&tmp_path, &path)?;
+
+    Ok(path)
+}
+
+/// Streams a snapshot into a RAM index after validating its versions, target and checksums. Archives unpacking
+/// to more than `max_bytes` of index files are rejected before they are loaded into memory.
+pub fn import(path: &Path, entity: Entity, language: Language, max_bytes: u64) -> anyhow::Result<(Index, SnapshotManifest)> {
+    let file = File::open(path).with_context(|| format!("failed reading snapshot {}", path.display()))?;
+    if file.metadata()?.len() > max_bytes {
+        bail!("snapshot {} is larger than {max_bytes} bytes", path.display());
+    }
+
+    let mut decoder = GzDecoder::new(BufReader::new(file));
+    let mut archive = tar::Archive::new(&mut decoder);
+    let mut entries = archive.entries()?;
+
+    let mut manifest_entry = entries.next().ok_or_else(|| anyhow!("snapshot {} is empty", path.display()))??;
+    if manifest_entry.path()?.to_string_lossy() != MANIFEST_ENTRY {
+        bail!("snapshot {} does not start with its manifest", path.display());
+    }
+    let mut bytes = Vec::new();
+    manifest_entry.by_ref().take(max_bytes).read_to_end(&mut bytes)?;
+    let manifest = serde_json::from_slice::<SnapshotManifest>(&bytes)?;
+
+    if manifest.format_version() != SNAPSHOT_FORMAT_VERSION || manifest.schema_version() != SNAPSHOT_SCHEMA_VERSION {
+        bail!("snapshot {} has format {} schema {}, expected format {SNAPSHOT_FORMAT_VERSION} schema {SNAPSHOT_SCHEMA_VERSION}"
+            , path.display(), manifest.format_version(), manifest.schema_version());
+    }
+    if manifest.entity() != <&str>::from(entity) || manifest.language() != <&str>::from(language) {
+        bail!("snapshot {} belongs to {} {}", path.display(), manifest.entity(), manifest.language());
+    }
+    if !manifest.is_consistent() {
+        bail!("checksum mismatch for snapshot {}", path.display());
+    }
+    if manifest.files().iter().map(SnapshotFile::size).sum::<u64>() > max_bytes {
+        bail!("snapshot {} unpacks to more than {max_bytes} bytes", path.display());
+    }
+
+    let directory = RamDirectory::create();
+    let mut loaded = HashSet::new();
+    for entry in entries {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        let Some(file) = name.strip_prefix(INDEX_ENTRY_PREFIX)
+            .and_then(|file_name| manifest.files().iter().find(|file| file.name() == file_name)) else {
+            bail!("snapshot {} holds unexpected file {name}", path.display());
+        };
+        if entry.size() != file.size() || !loaded.insert(file.name()) {
+            bail!("size mismatch for file {} of snapshot {}", file.name(), path.display());
+        }
+
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        if hex::encode(Sha256::digest(&bytes)) != file.sha256() {
+            bail!("checksum mismatch for file {} of snapshot {}", file.name(), path.display());
+        }
+        directory.atomic_write(Path::new(file.name()), &bytes)?;
+    }
+    if let Some(file) = manifest.files().iter().find(|file| !loaded.contains(file.name())) {
+        bail!("snapshot {} is missing file {}", path.display(), file.name());
+    }
+    // Reading up to the end of the stream checks the gzip trailer, catching a truncated or altered archive
+    io::copy(&mut decoder, &mut io::sink()).with_context(|| format!("corrupted snapshot {}", path.display()))?;
+
+    Ok((Index::open(directory)?, manifest))
+}
+
+pub fn created_at(manifest: &SnapshotManifest) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(manifest.created_at())
+}
+
+fn append<W: Write>(archive: &mut tar::Builder<W>, name: &str, bytes: &[u8]) -> anyhow::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive.append_data(&mut header, name, bytes)?;
+    Ok(())
+}
@@ -1,50 +1,100 @@
 use std::sync::Arc;
+use std::time::Instant;
 
+use crate::infrastructure::metrics::Metrics;
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::models::doc_details::DocDetails;
+use crate::models::entity::Entity;
 use crate::models::language::Language;
+use crate::services::blocklist_service_impl::BlocklistServiceImpl;
 use crate::services::doc_details_retriever::DocDetailsRetriever;
 use crate::services::index_processor::IndexWriter;
 
-pub struct IndexTask<T1, T2>
+pub struct IndexTask<T>
 where
-    T1: DocDetailsRetriever + Sync + Send,
-    T2: IndexWriter + Send + Sync,
+    T: IndexWriter + Send + Sync,
 {
-    data_retriever: T1,
-    index_writer: Arc<T2>,
+    entity: Entity,
+    data_retriever: Arc<dyn DocDetailsRetriever>,
+    index_writer: Arc<T>,
+    blocklist: Arc<BlocklistServiceImpl>,
+    metrics: Arc<Metrics>,
     limit: u64,
 }
 
-impl<T1, T2> IndexTask<T1, T2>
+impl<T> IndexTask<T>
 where
-    T1: DocDetailsRetriever + Sync + Send,
-    T2: IndexWriter + Send + Sync,
+    T: IndexWriter + Send + Sync,
 {
-    pub fn new(limit: u64, data_retriever: T1, index_writer: Arc<T2>) -> Self {
+    pub fn new(entity: Entity, limit: u64, data_retriever: Arc<dyn DocDetailsRetriever>, index_writer: Arc<T>, blocklist: Arc<BlocklistServiceImpl>, metrics: Arc<Metrics>) -> Self {
         Self {
+            entity,
             data_retriever,
             index_writer,
+            blocklist,
+            metrics,
             limit,
         }
     }
 
-    pub async fn start(&self) -> anyhow::Result<()> {
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Rebuilds every language index. On shutdown the batch being fetched is completed and
+    /// the rebuild is abandoned, leaving the live index untouched.
+    #[tracing::instrument(name = "index_task", skip_all, fields(entity = <&str>::from(self.entity)))]
+    pub async fn start(&self, shutdown: &ShutdownHandle) -> anyhow::Result<()> {
         for lang in Language::all() {
-            let mut results = Vec::new();
-            let mut offset = 0;
-            loop {
-                let entries = self.data_retriever.retrieve(lang.into(), self.limit, offset).await?;
-                if entries.is_empty() {
-                    break;
-                }
-                offset += self.limit;
-                results.extend(entries);
+            if !self.build(lang, shutdown).await? {
+                log::info!("reindex cancelled by shutdown, keeping current index");
+                return Ok(());
             }
-
-            self.index_writer.swap_index(lang, &results)?;
         }
 
         Ok(())
     }
-}
 
+    /// Rebuilds the index of `lang`, answering false when cancelled by shutdown.
+    #[tracing::instrument(skip_all, fields(language = <&str>::from(lang)))]
+    async fn build(&self, lang: Language, shutdown: &ShutdownHandle) -> anyhow::Result<bool> {
+        let started_at = Instant::now();
+        // Live writes applied from now on may be missed by the fetched rows, they are replayed on the swap
+        self.index_writer.begin_build(lang);
+        let fetched = self.fetch(lang, shutdown).await;
+        let Ok(Some(mut results)) = fetched else {
+            self.index_writer.cancel_build(lang);
+            return fetched.map(|_| false);
+        };
 
+        // Read right before the swap to skip the ids blocked while fetching
+        let blocked = self.blocklist.blocked_ids(self.entity);
+        if !blocked.is_empty() {
+            results.retain(|doc| !blocked.contains(&doc.id()));
+        }
+        if let Err(err) = self.index_writer.swap_index(lang, &results) {
+            self.index_writer.cancel_build(lang);
+            return Err(err);
+        }
+        self.metrics.observe_index_build(self.entity, lang, started_at.elapsed(), results.len());
+        tracing::info!(documents = results.len(), elapsed_ms = started_at.elapsed().as_millis() as u64, "index built");
+        Ok(true)
+    }
+
+    /// Every document of `lang` in the data source, `None` when cancelled by shutdown.
+    async fn fetch(&self, lang: Language, shutdown: &ShutdownHandle) -> anyhow::Result<Option<Vec<DocDetails>>> {
+        let mut results = Vec::new();
+        let mut offset = 0;
+        loop {
+            if shutdown.is_shutdown() {
+                return Ok(None);
+            }
+            let entries = self.data_retriever.retrieve(lang.into(), self.limit, offset).await?;
+            if entries.is_empty() {
+                return Ok(Some(results));
+            }
+            offset += self.limit;
+            results.extend(entries);
+        }
+    }
+}
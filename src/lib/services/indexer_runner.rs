@@ -1,56 +1,161 @@
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::sync::{mpsc};
-use tokio::sync::mpsc::Receiver;
+use anyhow::Context;
+use chrono::Utc;
+use tokio::sync::{mpsc, Mutex};
+use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::time;
+use tokio_util::task::TaskTracker;
 
-use crate::config::CONFIG;
-use crate::infrastructure::di_container::{DIContainer, GAME_INDEX_PROCESSOR_DEP, MOVIE_INDEX_PROCESSOR_DEP, RECIPE_INDEX_PROCESSOR_DEP, TV_INDEX_PROCESSOR_DEP};
-use crate::repositories::game_repository_impl::GameRepositoryImpl;
-use crate::repositories::movie_repository_impl::MovieRepositoryImpl;
-use crate::repositories::recipe_repository_impl::RecipeRepositoryImpl;
-use crate::repositories::tv_repository_impl::TvRepositoryImpl;
-use crate::services::impls::game_doc_details_retriever::GameDocDetailsRetriever;
-use crate::services::impls::movie_doc_details_retriever::MovieDocDetailsRetriever;
-use crate::services::impls::recipe_doc_details_retriever::RecipeDocDetailsRetriever;
-use crate::services::impls::tv_doc_details_retriever::TvDocDetailsRetriever;
-use crate::services::index_processor::IndexProcessor;
+use crate::config::Config;
+use crate::infrastructure::di_container::{BLOCKLIST_SERVICE_IMPL_DEP, CONFIG_DEP, DIContainer, doc_details_retriever_dep, index_processor_dep, METRICS_DEP};
+use crate::infrastructure::shutdown::ShutdownHandle;
+use crate::models::entity::Entity;
+use crate::services::index_processor::{IndexProcessor, IndexWriter};
+use crate::services::index_schedule::IndexSchedule;
 use crate::services::index_task::IndexTask;
 
-
-#[derive(Default)]
-pub struct IndexerRunner;
+pub struct IndexerRunner {
+    shutdown: ShutdownHandle,
+    tracker: TaskTracker,
+    /// Held while the index of an entity is rebuilt, so rebuilds of the same entity never overlap.
+    running: HashMap<Entity, Arc<Mutex<()>>>,
+}
 
 
 impl IndexerRunner {
-    pub fn run(&self, di_container: &DIContainer) -> Receiver<()> {
-        let (tx, rv) = mpsc::channel::<()>(1);
-        let batch_size = CONFIG.indexer_runner().batch_size();
-        let interval = CONFIG.indexer_runner().interval();
-
-        let movie_indexer = IndexTask::<MovieDocDetailsRetriever<MovieRepositoryImpl>
-            , IndexProcessor>::new(batch_size, MovieDocDetailsRetriever::new(di_container), di_container.get::<IndexProcessor>(MOVIE_INDEX_PROCESSOR_DEP));
-        let tv_indexer = IndexTask::<TvDocDetailsRetriever<TvRepositoryImpl>
-            , IndexProcessor>::new(batch_size, TvDocDetailsRetriever::new(di_container), di_container.get::<IndexProcessor>(TV_INDEX_PROCESSOR_DEP));
-        let recipe_indexer = IndexTask::<RecipeDocDetailsRetriever<RecipeRepositoryImpl>
-            , IndexProcessor>::new(batch_size, RecipeDocDetailsRetriever::new(di_container), di_container.get::<IndexProcessor>(RECIPE_INDEX_PROCESSOR_DEP));
-        let game_indexer = IndexTask::<GameDocDetailsRetriever<GameRepositoryImpl>
-            , IndexProcessor>::new(batch_size, GameDocDetailsRetriever::new(di_container), di_container.get::<IndexProcessor>(GAME_INDEX_PROCESSOR_DEP));
-
-        tokio::spawn(async move {
-            let mut interval = time::interval(Duration::from_secs(interval));
-            interval.tick().await;
+    pub fn new(shutdown: ShutdownHandle) -> Self {
+        let running = Entity::all().into_iter().map(|entity| (entity, Arc::new(Mutex::new(())))).collect();
+        Self { shutdown, tracker: TaskTracker::new(), running }
+    }
+
+    pub fn run(&self, di_container: &DIContainer) -> anyhow::Result<Receiver<Entity>> {
+        let (tx, rv) = mpsc::channel::<Entity>(Entity::all().len());
+        let config = di_container.get(CONFIG_DEP)?;
+        for entity in Entity::all() {
+            self.schedule(&config, entity, Self::index_task(di_container, entity)?, tx.clone())?;
+        }
+
+        Ok(rv)
+    }
+
+    /// Rebuilds the indexes of the given entities once, sequentially, without scheduling further runs.
+    pub async fn run_once(&self, di_container: &DIContainer, entities: &[Entity]) -> anyhow::Result<()> {
+        for entity in entities {
+            self.reindex(&Self::index_task(di_container, *entity)?).await?;
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the index of the task entity now. Unlike scheduled runs, an in-progress rebuild is
+    /// waited for instead of skipped, as it may have read its rows before the changes to catch up with.
+    pub async fn reindex<T>(&self, index_task: &IndexTask<T>) -> anyhow::Result<()>
+    where
+        T: IndexWriter + Send + Sync,
+    {
+        let entity_name: &str = index_task.entity().into();
+        let running = self.running(index_task.entity());
+        let _running = running.lock().await;
+        log::info!("starting reindex of {entity_name} content...");
+        index_task.start(&self.shutdown).await
+    }
+
+    pub(crate) fn index_task(di_container: &DIContainer, entity: Entity) -> anyhow::Result<IndexTask<IndexProcessor>> {
+        let config = di_container.get(CONFIG_DEP)?;
+        Ok(IndexTask::new(
+            entity,
+            config.indexer_runner().batch_size(),
+            di_container.get(doc_details_retriever_dep(entity))?,
+            di_container.get(index_processor_dep(entity))?,
+            di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?,
+            di_container.get(METRICS_DEP)?,
+        ))
+    }
+
+    /// Stops scheduling new runs and waits up to `timeout` for in-flight ones to finish their current batch.
+    pub async fn stop(&self, timeout: Duration) {
+        self.shutdown.shutdown();
+        self.tracker.close();
+
+        log::info!("waiting for in-flight reindex to finish...");
+        if time::timeout(timeout, self.tracker.wait()).await.is_err() {
+            log::warn!("in-flight reindex exceeded {}s, abandoning it", timeout.as_secs());
+        }
+    }
+
+    fn schedule<T>(&self, config: &Config, entity: Entity, index_task: IndexTask<T>, tx: Sender<Entity>) -> anyhow::Result<()>
+    where
+        T: IndexWriter + Send + Sync + 'static,
+    {
+        let entity_name: &str = entity.into();
+        let schedule = IndexSchedule::new(&config.indexer_runner().schedule(entity))
+            .with_context(|| format!("invalid indexer schedule for {entity_name}"))?;
+        let retry_interval = Duration::from_secs(config.indexer_runner().retry_interval());
+        let index_task = Arc::new(index_task);
+        let running = self.running(entity);
+        let shutdown = self.shutdown.clone();
+        let tracker = self.tracker.clone();
+
+        self.tracker.spawn(async move {
+            // Initial build always runs at startup, regardless of schedule or blackout window
+            let mut built = Self::execute(entity, &index_task, &running, &shutdown).await;
+            let _ = tx.send(entity).await;
+
+            // Restored or empty indexes are served meanwhile, so keep retrying instead of waiting a full schedule
+            while !built {
+                log::warn!("retrying initial reindex of {entity_name} content in {}s", retry_interval.as_secs());
+                tokio::select! {
+                    _ = time::sleep(retry_interval) => {}
+                    _ = shutdown.wait() => return,
+                }
+                built = Self::execute(entity, &index_task, &running, &shutdown).await;
+            }
+
             loop {
-                log::info!("starting reindex of all content...");
-                let _ = movie_indexer.start().await;
-                let _ = tv_indexer.start().await;
-                let _ = recipe_indexer.start().await;
-                let _ = game_indexer.start().await;
-                let _ = tx.send(()).await;
-                interval.tick().await;
+                let Some(next_run) = schedule.next_run(Utc::now()) else {
+                    log::warn!("no upcoming reindex found for {entity_name}, stopping its schedule");
+                    break;
+                };
+                let delay = (next_run - Utc::now()).to_std().unwrap_or_default();
+                tokio::select! {
+                    _ = time::sleep(delay) => {}
+                    _ = shutdown.wait() => break,
+                }
+
+                let index_task = index_task.clone();
+                let running = running.clone();
+                let shutdown = shutdown.clone();
+                tracker.spawn(async move {
+                    Self::execute(entity, &index_task, &running, &shutdown).await;
+                });
             }
         });
 
-        rv
+        Ok(())
+    }
+
+    fn running(&self, entity: Entity) -> Arc<Mutex<()>> {
+        self.running[&entity].clone()
+    }
+
+    /// Runs a reindex unless one is already in progress, returns `false` only when the reindex failed.
+    async fn execute<T>(entity: Entity, index_task: &IndexTask<T>, running: &Mutex<()>, shutdown: &ShutdownHandle) -> bool
+    where
+        T: IndexWriter + Send + Sync,
+    {
+        let entity_name: &str = entity.into();
+        let Ok(_running) = running.try_lock() else {
+            log::warn!("skipping reindex of {entity_name} content, previous run still in progress");
+            return true;
+        };
+
+        log::info!("starting reindex of {entity_name} content...");
+        let result = index_task.start(shutdown).await;
+        if let Err(err) = &result {
+            log::error!("reindex of {entity_name} content failed: {err:?}");
+        }
+        result.is_ok()
     }
 }
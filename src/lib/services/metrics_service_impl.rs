@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sqlx::{Pool, Postgres};
+
+use crate::infrastructure::di_container::{DB_POOL_DEP, DIContainer, GAME_INDEX_PROCESSOR_DEP, METRICS_DEP, MOVIE_INDEX_PROCESSOR_DEP, RECIPE_INDEX_PROCESSOR_DEP, TV_INDEX_PROCESSOR_DEP, SEARCH_SERVICE_IMPL_DEP};
+use crate::infrastructure::metrics::Metrics;
+use crate::models::entity::Entity;
+use crate::models::language::Language;
+use crate::services::index_processor::IndexProcessor;
+use crate::services::search_service_impl::SearchServiceImpl;
+
+pub struct MetricsServiceImpl {
+    metrics: Arc<Metrics>,
+    db_pool: Arc<Pool<Postgres>>,
+    index_processors: HashMap<Entity, Arc<IndexProcessor>>,
+    search_service: Arc<SearchServiceImpl>,
+}
+
+impl MetricsServiceImpl {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
+        let mut index_processors = HashMap::new();
+        index_processors.insert(Entity::Movie, di_container.get(MOVIE_INDEX_PROCESSOR_DEP)?);
+        index_processors.insert(Entity::Tv, di_container.get(TV_INDEX_PROCESSOR_DEP)?);
+        index_processors.insert(Entity::Recipe, di_container.get(RECIPE_INDEX_PROCESSOR_DEP)?);
+        index_processors.insert(Entity::Game, di_container.get(GAME_INDEX_PROCESSOR_DEP)?);
+
+        Ok(Self {
+            metrics: di_container.get(METRICS_DEP)?,
+            db_pool: di_container.get(DB_POOL_DEP)?,
+            index_processors,
+            search_service: di_container.get(SEARCH_SERVICE_IMPL_DEP)?,
+        })
+    }
+}
+
+pub trait MetricsService {
+    fn render(&self) -> anyhow::Result<String>;
+}
+
+impl MetricsService for MetricsServiceImpl {
+    fn render(&self) -> anyhow::Result<String> {
+        // Gauges sampled at scrape time instead of being pushed on every change
+        self.metrics.set_db_pool(self.db_pool.size(), self.db_pool.num_idle());
+        self.metrics.set_search_cache_entries(self.search_service.cache_entries());
+        for (entity, index_processor) in self.index_processors.iter() {
+            for language in Language::all() {
+                self.metrics.set_index_segments(*entity, language, index_processor.segment_count(language));
+                let age = index_processor.last_build(language).and_then(|last_build| last_build.elapsed().ok());
+                self.metrics.set_index_age(*entity, language, age, index_processor.is_stale(language));
+            }
+        }
+
+        self.metrics.encode()
+    }
+}
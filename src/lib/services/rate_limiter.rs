@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+/// Token bucket of `capacity` tokens refilled continuously at `refill_per_second`.
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Fixed window of `limit` requests per `period`, starting at the first request after the previous one ended.
+pub struct QuotaWindow {
+    limit: u64,
+    period: Duration,
+    used: u64,
+    started_at: Option<Instant>,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, refill_per_second: f64, now: Instant) -> Self {
+        Self { capacity: capacity as f64, refill_per_second, tokens: capacity as f64, updated_at: now }
+    }
+
+    /// Time until a token is available, `None` if one is available now.
+    pub fn retry_after(&mut self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.updated_at = now;
+
+        if self.tokens >= 1.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_second))
+    }
+
+    /// Consumes a token, to be called after `retry_after` answered `None`.
+    pub fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+
+    pub fn tokens(&self) -> f64 {
+        self.tokens
+    }
+}
+
+impl QuotaWindow {
+    pub fn new(limit: u64, period: Duration) -> Self {
+        Self { limit, period, used: 0, started_at: None }
+    }
+
+    /// Time until the current window ends if its quota is exhausted, `None` if requests are still allowed.
+    pub fn retry_after(&mut self, now: Instant) -> Option<Duration> {
+        let started_at = *self.started_at.get_or_insert(now);
+        let ends_at = started_at + self.period;
+        if now >= ends_at {
+            self.started_at = Some(now);
+            self.used = 0;
+            return None;
+        }
+
+        (self.used >= self.limit).then(|| ends_at - now)
+    }
+
+    /// Counts a request, to be called after `retry_after` answered `None`.
+    pub fn take(&mut self) {
+        self.used += 1;
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+}
@@ -0,0 +1,13 @@
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::models::query_report::QueryCount;
+use crate::models::search_event::SearchEvent;
+
+#[async_trait]
+pub trait SearchEventSink: Send + Sync {
+    async fn write(&self, events: &[SearchEvent]) -> anyhow::Result<()>;
+
+    /// Most searched queries since `since`, optionally only the ones without hits.
+    async fn top_queries(&self, since: DateTime<Utc>, zero_hits_only: bool, limit: usize) -> anyhow::Result<Vec<QueryCount>>;
+}
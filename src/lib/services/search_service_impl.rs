@@ -1,28 +1,51 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::Context;
+use moka::sync::Cache;
 
-use crate::infrastructure::di_container::{DIContainer, GAME_INDEX_PROCESSOR_DEP, MOVIE_INDEX_PROCESSOR_DEP, RECIPE_INDEX_PROCESSOR_DEP, TV_INDEX_PROCESSOR_DEP};
+use crate::config::search_cache_config::SearchCacheConfig;
+use crate::infrastructure::di_container::{BLOCKLIST_SERVICE_IMPL_DEP, CONFIG_DEP, CURATION_SERVICE_IMPL_DEP, DIContainer, FEEDBACK_SERVICE_IMPL_DEP, GAME_INDEX_PROCESSOR_DEP, index_processor_dep, METRICS_DEP, MOVIE_INDEX_PROCESSOR_DEP, RECIPE_INDEX_PROCESSOR_DEP, TV_INDEX_PROCESSOR_DEP};
+use crate::infrastructure::metrics::Metrics;
 use crate::models::entity::Entity;
 use crate::models::language::Language;
-use crate::services::index_processor::{IndexProcessor, IndexSearcher};
+use crate::models::search_error::SearchError;
+use crate::models::search_event::SearchEvent;
+use crate::services::blocklist_service_impl::{BlocklistService, BlocklistServiceImpl};
+use crate::services::curation_service_impl::{CurationService, CurationServiceImpl};
+use crate::services::feedback_service_impl::{FeedbackService, FeedbackServiceImpl};
+use crate::services::index_processor::IndexSearcher;
 
 pub struct SearchServiceImpl {
     searchers: HashMap<Entity, Arc<dyn IndexSearcher + Send + Sync>>,
+    cache: Option<Cache<SearchCacheKey, Arc<Vec<u64>>>>,
+    feedback: Arc<FeedbackServiceImpl>,
+    curation: Arc<CurationServiceImpl>,
+    blocklist: Arc<BlocklistServiceImpl>,
+    metrics: Arc<Metrics>,
+}
+
+/// The generation keeps results computed against a replaced index unreachable, even when they are
+/// inserted after the invalidation of their entity and language.
+#[derive(Hash, PartialEq, Eq)]
+struct SearchCacheKey {
+    entity: Entity,
+    language: Language,
+    generation: u64,
+    query: String,
 }
 
 impl SearchServiceImpl {
-    pub fn new(di_container: &DIContainer) -> Self {
+    pub fn new(di_container: &DIContainer) -> anyhow::Result<Self> {
         let mut searchers = HashMap::new();
 
-        let movie_index_processor = di_container.get::<IndexProcessor>(MOVIE_INDEX_PROCESSOR_DEP)
+        let movie_index_processor = di_container.get(MOVIE_INDEX_PROCESSOR_DEP)?
             as Arc<dyn IndexSearcher + Send + Sync>;
-        let tv_index_processor = di_container.get::<IndexProcessor>(TV_INDEX_PROCESSOR_DEP)
+        let tv_index_processor = di_container.get(TV_INDEX_PROCESSOR_DEP)?
             as Arc<dyn IndexSearcher + Send + Sync>;
-        let recipe_index_processor = di_container.get::<IndexProcessor>(RECIPE_INDEX_PROCESSOR_DEP)
+        let recipe_index_processor = di_container.get(RECIPE_INDEX_PROCESSOR_DEP)?
             as Arc<dyn IndexSearcher + Send + Sync>;
-        let game_index_processor = di_container.get::<IndexProcessor>(GAME_INDEX_PROCESSOR_DEP)
+        let game_index_processor = di_container.get(GAME_INDEX_PROCESSOR_DEP)?
             as Arc<dyn IndexSearcher + Send + Sync>;
 
         searchers.insert(Entity::Movie, movie_index_processor);
@@ -30,19 +53,133 @@ impl SearchServiceImpl {
         searchers.insert(Entity::Recipe, recipe_index_processor);
         searchers.insert(Entity::Game, game_index_processor);
 
-        Self { searchers }
+        let cache = Self::cache(di_container, di_container.get(CONFIG_DEP)?.search_cache())?;
+        Ok(Self {
+            searchers,
+            cache,
+            feedback: di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?,
+            curation: di_container.get(CURATION_SERVICE_IMPL_DEP)?,
+            blocklist: di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?,
+            metrics: di_container.get(METRICS_DEP)?,
+        })
+    }
+
+    fn cache(di_container: &DIContainer, config: &SearchCacheConfig) -> anyhow::Result<Option<Cache<SearchCacheKey, Arc<Vec<u64>>>>> {
+        if !config.enabled() {
+            return Ok(None);
+        }
+
+        let mut builder = Cache::builder()
+            .max_capacity(config.max_entries())
+            .support_invalidation_closures();
+        if let Some(ttl) = config.ttl() {
+            builder = builder.time_to_live(Duration::from_secs(ttl));
+        }
+        let cache: Cache<SearchCacheKey, Arc<Vec<u64>>> = builder.build();
+
+        for entity in Entity::all() {
+            let cache = cache.clone();
+            di_container.get(index_processor_dep(entity))?.on_change(move |language| {
+                if let Err(err) = cache.invalidate_entries_if(move |key, _| key.entity == entity && key.language == language) {
+                    log::warn!("failed invalidating cached {} searches: {err}", <&str>::from(entity));
+                }
+            });
+        }
+        // Clicks reorder the results of their query only
+        let clicked = cache.clone();
+        di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?.on_click(move |entity, language, query| {
+            let query = query.to_string();
+            if let Err(err) = clicked.invalidate_entries_if(move |key, _| key.entity == entity && key.language == language && key.query == query) {
+                log::warn!("failed invalidating cached {} searches: {err}", <&str>::from(entity));
+            }
+        });
+        Ok(Some(cache))
+    }
+
+    /// Cached search results, 0 when the cache is disabled. Pending evictions and invalidations are
+    /// applied first since the count is otherwise only eventually consistent.
+    pub fn cache_entries(&self) -> u64 {
+        self.cache.as_ref()
+            .map(|cache| {
+                cache.run_pending_tasks();
+                cache.entry_count()
+            })
+            .unwrap_or_default()
+    }
+
+    fn searcher(&self, entity: Entity) -> Result<&Arc<dyn IndexSearcher + Send + Sync>, SearchError> {
+        self.searchers.get(&entity)
+            .ok_or_else(|| SearchError::SearcherNotFound(<&str>::from(entity).to_string()))
+    }
+
+    /// Index hits of `query` boosted by click feedback, from the cache when enabled.
+    fn retrieve(&self, entity: Entity, lang: Language, tokens: &[&str], query: String) -> Result<Vec<u64>, SearchError> {
+        let searcher = self.searcher(entity)?;
+        let Some(cache) = &self.cache else {
+            return searcher.search_boosted(lang, tokens, &self.feedback.boosts(entity, lang, &query));
+        };
+        let key = SearchCacheKey { entity, language: lang, generation: searcher.generation(lang), query };
+        if let Some(result) = cache.get(&key) {
+            tracing::Span::current().record("cache", "hit");
+            self.metrics.observe_search_cache(entity, lang, true);
+            return Ok(result.as_ref().clone());
+        }
+
+        tracing::Span::current().record("cache", "miss");
+        self.metrics.observe_search_cache(entity, lang, false);
+        let result = searcher.search_boosted(lang, tokens, &self.feedback.boosts(entity, lang, &key.query))?;
+        cache.insert(key, Arc::new(result.clone()));
+        Ok(result)
     }
 }
 
 pub trait SearchService {
-    fn search(&self, keywords: &mut str, lang: Language, entity: Entity) -> anyhow::Result<Vec<u64>>;
+    fn search(&self, keywords: &mut str, lang: Language, entity: Entity) -> Result<Vec<u64>, SearchError>;
+
+    /// Generation of the index searched for `entity` and `lang`, see [`IndexSearcher::generation`].
+    fn generation(&self, lang: Language, entity: Entity) -> Result<u64, SearchError>;
+
+    /// Identifier of the curation rules applied to the results, 0 when there are none.
+    fn rules_fingerprint(&self) -> u64;
+
+    /// Identifier of the click feedback boosting the results of `keywords`, see [`FeedbackService::version`].
+    fn feedback_version(&self, keywords: &str, lang: Language, entity: Entity) -> u64;
+
+    /// Same as [`SearchService::search`] keeping only the `limit` best hits.
+    fn search_top(&self, keywords: &mut str, lang: Language, entity: Entity, limit: usize) -> Result<Vec<u64>, SearchError> {
+        let mut result = self.search(keywords, lang, entity)?;
+        result.truncate(limit);
+        Ok(result)
+    }
 }
-impl SearchService for SearchServiceImpl {
-    fn search(&self, keywords: &mut str, lang: Language, entity: Entity) -> anyhow::Result<Vec<u64>> {
-        let searcher = self.searchers.get(&entity).context("SEARCHER NOT FOUND")?;
 
+impl SearchService for SearchServiceImpl {
+    #[tracing::instrument(skip(self, keywords), fields(cache = tracing::field::Empty))]
+    fn search(&self, keywords: &mut str, lang: Language, entity: Entity) -> Result<Vec<u64>, SearchError> {
         keywords.make_ascii_lowercase();
         let tokens = keywords.split_whitespace().collect::<Vec<&str>>();
-        searcher.search(lang, &tokens)
+        if tokens.is_empty() {
+            return Err(SearchError::EmptyQuery);
+        }
+
+        // Rules apply after the cache, so editing them takes effect without invalidating it
+        let query = tokens.join(" ");
+        let mut result = self.retrieve(entity, lang, &tokens, query.clone())?;
+        self.curation.apply(entity, lang, &query, &mut result);
+        // Last, so neither the cache, a stale snapshot nor a pin brings back a blocked document
+        self.blocklist.filter(entity, &mut result);
+        Ok(result)
+    }
+
+    fn generation(&self, lang: Language, entity: Entity) -> Result<u64, SearchError> {
+        Ok(self.searcher(entity)?.generation(lang))
+    }
+
+    fn rules_fingerprint(&self) -> u64 {
+        self.curation.fingerprint()
+    }
+
+    fn feedback_version(&self, keywords: &str, lang: Language, entity: Entity) -> u64 {
+        self.feedback.version(entity, lang, &SearchEvent::normalize(keywords))
     }
 }
\ No newline at end of file
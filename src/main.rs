@@ -1,12 +1,8 @@
-use lib::infrastructure::app_runner::AppRunner;
+use clap::Parser;
+
+use lib::cli::Cli;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    AppRunner::run(None).await
+    Cli::parse().run().await
 }
-
-
-
-
-
-
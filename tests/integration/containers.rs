@@ -1,37 +1,28 @@
-use std::{env, fs, mem, thread};
-use std::borrow::Cow;
-use std::cell::Cell;
-use std::error::Error;
-use std::future::Future;
-use std::pin::Pin;
-use std::process::{Command, ExitStatus, Output};
-use std::sync::{LazyLock, mpsc, Mutex};
+use std::{env, thread};
+use std::sync::{LazyLock, OnceLock};
 use std::time::Duration;
 
 use ctor::{ctor, dtor};
-use reqwest::{Client, StatusCode};
-use sqlx::postgres;
-use testcontainers::{Container, GenericImage, Image, ImageExt};
-use testcontainers::core::{ContainerPort, ExecCommand, IntoContainerPort, Mount, WaitFor};
+use reqwest::{Client, Response, StatusCode};
+use testcontainers::{Container, GenericImage, ImageExt};
+use testcontainers::core::{IntoContainerPort, Mount, WaitFor};
 use testcontainers::core::logs::LogSource;
-use testcontainers::core::wait::{HealthWaitStrategy, LogWaitStrategy};
-use testcontainers::core::WaitFor::Healthcheck;
+use testcontainers::core::wait::LogWaitStrategy;
 use testcontainers::runners::SyncRunner;
 use tokio::runtime;
 use tokio::sync::oneshot;
-use tokio::time::sleep;
 
-use lib::config::{CONFIG, Config, CONFIG_PATH_ENV};
-use lib::handlers::requests::search_request::SearchRequest;
+use lib::config::{Config, CONFIG_PATH_ENV};
 use lib::infrastructure::app_runner::AppRunner;
-use lib::infrastructure::di_container::DIContainer;
-use lib::infrastructure::http_server::HttpServer;
-use lib::services::indexer_runner::IndexerRunner;
+use lib::infrastructure::shutdown::ShutdownHandle;
 
-pub const CONFIG_FOLDER_PATH: &'static str = "/tests/integration/config";
-pub const INIT_SQL_PATH: &'static str = "/db/init.sql";
-pub const CONFIG_FILE_PATH: &'static str = "/Config-Test.toml";
+pub const CONFIG_FOLDER_PATH: &str = "/tests/integration/config";
+pub const INIT_SQL_PATH: &str = "/db/init.sql";
+pub const CONFIG_FILE_PATH: &str = "/Config-Test.toml";
+pub const DATABASE_HOST_ENV: &str = "APP__DATABASE__HOST";
+pub const DATABASE_PORT_ENV: &str = "APP__DATABASE__PORT";
 
+static SHUTDOWN_HANDLE: OnceLock<ShutdownHandle> = OnceLock::new();
 
 static POSTGRES_CONTAINER: LazyLock<Container<GenericImage>> = LazyLock::new(|| {
     let current_dir = env::current_dir().unwrap();
@@ -51,19 +42,21 @@ static POSTGRES_CONTAINER: LazyLock<Container<GenericImage>> = LazyLock::new(||
 
 #[ctor]
 fn init() {
-    env_logger::builder()
-        .filter_level(log::LevelFilter::Trace)
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
         .init();
 
     let current_dir = env::current_dir().unwrap();
     let config_path = format!("{}{CONFIG_FOLDER_PATH}{CONFIG_FILE_PATH}", current_dir.to_str().unwrap());
     env::set_var(CONFIG_PATH_ENV, &config_path);
 
-    let (tx, rx) = oneshot::channel();
+    let pg_container_port = POSTGRES_CONTAINER.get_host_port_ipv4(5432).expect("Postgres Container Port retrieved");
+    let pg_container_host = POSTGRES_CONTAINER.get_host().expect("Postgres Container Port retrieved").to_string();
+    env::set_var(DATABASE_HOST_ENV, pg_container_host);
+    env::set_var(DATABASE_PORT_ENV, pg_container_port.to_string());
 
-    let pg_container_port = (&*POSTGRES_CONTAINER).get_host_port_ipv4(5432).expect("Postgres Container Port retrieved");
-    let pg_container_host = (&*POSTGRES_CONTAINER).get_host().expect("Postgres Container Port retrieved").to_string();
-    let (old_host, old_port) = change_db_config(&config_path, pg_container_host, pg_container_port).expect("Postgres Port changed");
+    let config = Config::load().expect("Configuration loaded");
+    let (tx, rx) = oneshot::channel();
 
     thread::spawn(move || {
         runtime::Builder::new_current_thread()
@@ -71,34 +64,20 @@ fn init() {
             .build()
             .unwrap()
             .block_on(async {
-                let _ = AppRunner::run(Some(tx)).await;
+                let _ = AppRunner::run(config, Some(tx)).await;
             });
     });
 
     log::info!("waiting to web server to be fully initialized...");
-    rx.blocking_recv().expect("Received started signal");
-
-    change_db_config(&config_path, old_host, old_port).expect("Postgres Port changed");
-}
-
-fn change_db_config(config_path: &str, host: String, port: u16) -> anyhow::Result<(String, u16)> {
-    let content = fs::read_to_string(config_path)?;
-
-    let mut config: Config = toml::from_str(&content)?;
-    let old_port = config.database().port();
-    let old_host = config.database().host().to_string();
-
-    config.database_mut().set_port(port);
-    config.database_mut().set_host(host);
-
-    let new_content = toml::to_string(&config)?;
-    fs::write(config_path, new_content)?;
-
-    Ok((old_host, old_port))
+    let shutdown_handle = rx.blocking_recv().expect("Received started signal");
+    let _ = SHUTDOWN_HANDLE.set(shutdown_handle);
 }
 
 #[dtor]
 fn destroy() {
+    if let Some(shutdown_handle) = SHUTDOWN_HANDLE.get() {
+        shutdown_handle.shutdown();
+    }
     //(&*POSTGRES_CONTAINER).stop();
 }
 
@@ -121,23 +100,69 @@ where
     Ok(result)
 }
 
-pub async fn no_output_check_post<I>(endpoint: &str, input: &I, status_code: StatusCode) -> anyhow::Result<()>
+pub async fn check_get<O>(endpoint: &str, status_code: StatusCode) -> anyhow::Result<O>
 where
-    I: serde::ser::Serialize,
+    O: for<'de> serde::Deserialize<'de>,
 {
     let client = Client::new();
     let response = client
-        .post(format!("http://localhost:8080{endpoint}"))
-        .json(input)
+        .get(format!("http://localhost:8080{endpoint}"))
         .send()
         .await?;
     assert_eq!(response.status(), status_code);
-    Ok(())
-}
 
+    let result: O = response.json().await?;
 
+    Ok(result)
+}
 
+pub async fn no_output_check_get(endpoint: &str, status_code: StatusCode) -> anyhow::Result<()> {
+    let client = Client::new();
+    let response = client
+        .get(format!("http://localhost:8080{endpoint}"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), status_code);
+    Ok(())
+}
 
+pub async fn text_check_get(endpoint: &str, status_code: StatusCode) -> anyhow::Result<String> {
+    let client = Client::new();
+    let response = client
+        .get(format!("http://localhost:8080{endpoint}"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), status_code);
+    Ok(response.text().await?)
+}
+
+pub async fn check_post_with_headers<I, O>(endpoint: &str, input: &I, headers: &[(&str, &str)], status_code: StatusCode) -> anyhow::Result<O>
+where
+    I: serde::ser::Serialize,
+    O: for<'de> serde::Deserialize<'de>,
+{
+    let client = Client::new();
+    let mut request = client
+        .post(format!("http://localhost:8080{endpoint}"))
+        .json(input);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await?;
+    assert_eq!(response.status(), status_code);
 
+    let result: O = response.json().await?;
 
+    Ok(result)
+}
 
+pub async fn response_get_with_headers(endpoint: &str, headers: &[(&str, &str)], status_code: StatusCode) -> anyhow::Result<Response> {
+    let client = Client::new();
+    let mut request = client.get(format!("http://localhost:8080{endpoint}"));
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await?;
+    assert_eq!(response.status(), status_code);
+    Ok(response)
+}
@@ -0,0 +1,46 @@
+use sqlx::{Pool, Postgres, query};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use lib::config::Config;
+use lib::infrastructure::app_runner::AppRunner;
+use lib::infrastructure::di_container::{DIContainer, GAME_REPOSITORY_DEP};
+
+const EN_ONLY_GAME_ID: i64 = 990001;
+
+async fn db_pool(config: &Config) -> anyhow::Result<Pool<Postgres>> {
+    let connect_options = PgConnectOptions::new()
+        .host(config.database().host())
+        .port(config.database().port())
+        .username(config.database().username())
+        .password(config.database().password())
+        .database(config.database().db_name());
+    Ok(PgPoolOptions::new().max_connections(1).connect_with(connect_options).await?)
+}
+
+#[tokio::test]
+async fn should_finds_games_only_in_the_languages_they_have_details_in() -> anyhow::Result<()> {
+    let config = Config::load()?;
+    let db_pool = db_pool(&config).await?;
+    query("INSERT INTO game.game (game_id, name) VALUES ($1, 'Untranslated Quest') ON CONFLICT DO NOTHING")
+        .bind(EN_ONLY_GAME_ID)
+        .execute(&db_pool)
+        .await?;
+    query("INSERT INTO game.game_details (id, game_id, language, summary) SELECT nextval('game.game_details_id_seq'), $1, 'EN', 'English only' WHERE NOT EXISTS (SELECT 1 FROM game.game_details WHERE game_id = $1)")
+        .bind(EN_ONLY_GAME_ID)
+        .execute(&db_pool)
+        .await?;
+
+    let di_container = AppRunner::build_container(DIContainer::new(), config, db_pool)?;
+    let game_repository = di_container.get(GAME_REPOSITORY_DEP)?;
+    let ids = [47530, EN_ONLY_GAME_ID as u64];
+
+    let english = game_repository.find_games_by_lang_and_ids("EN", &ids).await?;
+    assert_eq!(vec![47530, EN_ONLY_GAME_ID as u64], english.iter().map(|game| game.id()).collect::<Vec<_>>());
+    let spanish = game_repository.find_games_by_lang_and_ids("ES", &ids).await?;
+    assert_eq!(vec![47530], spanish.iter().map(|game| game.id()).collect::<Vec<_>>());
+
+    // Full builds list the same games as the lookups by id
+    let spanish = game_repository.find_games_by_lang_and_limit_offset("ES", 1000, 0).await?;
+    assert!(spanish.iter().all(|game| game.id() != EN_ONLY_GAME_ID as u64));
+    Ok(())
+}
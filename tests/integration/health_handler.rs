@@ -0,0 +1,21 @@
+use reqwest::StatusCode;
+
+use lib::handlers::responses::health_response::{HealthResponse, UP_STATUS};
+
+use crate::containers::{check_get, no_output_check_get};
+
+#[tokio::test]
+async fn should_returns_live() -> anyhow::Result<()> {
+    no_output_check_get("/health/live", StatusCode::OK).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_returns_ready_once_indexed() -> anyhow::Result<()> {
+    let response: HealthResponse = check_get("/health/ready", StatusCode::OK).await?;
+    assert_eq!(UP_STATUS, response.status());
+    assert_eq!(UP_STATUS, response.database());
+    assert_eq!(8, response.indexes().len());
+    assert!(response.indexes().iter().all(|index| index.status() == UP_STATUS && !index.stale()));
+    Ok(())
+}
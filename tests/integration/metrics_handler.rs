@@ -0,0 +1,25 @@
+use std::borrow::Cow;
+
+use reqwest::StatusCode;
+
+use lib::handlers::requests::search_request::SearchRequest;
+use lib::handlers::responses::problem_details::ProblemDetails;
+
+use crate::containers::{check_post, text_check_get};
+
+#[tokio::test]
+async fn should_returns_metrics() -> anyhow::Result<()> {
+    let request = SearchRequest::new("queen".to_string(), Cow::from("MOVIE"));
+    check_post::<_, Vec<u64>>("/run", &request, StatusCode::OK).await?;
+    let request = SearchRequest::new(" ".to_string(), Cow::from("MOVIE"));
+    check_post::<_, ProblemDetails>("/run", &request, StatusCode::BAD_REQUEST).await?;
+
+    let response = text_check_get("/metrics", StatusCode::OK).await?;
+    assert!(response.contains("content_search_search_duration_seconds_count{entity=\"MOVIE\",language=\"EN\",outcome=\"ok\"}"));
+    assert!(response.contains("content_search_search_duration_seconds_count{entity=\"MOVIE\",language=\"EN\",outcome=\"client_error\"}"));
+    assert!(response.contains("content_search_search_hits_bucket{entity=\"MOVIE\",language=\"EN\",le=\"1\"}"));
+    assert!(response.contains("content_search_index_documents{entity=\"GAME\",language=\"ES\"}"));
+    assert!(response.contains("content_search_index_segments"));
+    assert!(response.contains("content_search_db_pool_connections"));
+    Ok(())
+}
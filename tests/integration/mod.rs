@@ -1,2 +1,5 @@
 mod containers;
-mod search_handler;
\ No newline at end of file
+mod search_handler;
+mod health_handler;
+mod metrics_handler;
+mod game_repository;
\ No newline at end of file
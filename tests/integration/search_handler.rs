@@ -3,9 +3,12 @@ use std::borrow::Cow;
 use reqwest::StatusCode;
 use rstest::rstest;
 
+use lib::handlers::requests::batch_search_request::BatchSearchRequest;
 use lib::handlers::requests::search_request::SearchRequest;
+use lib::handlers::responses::batch_search_response::BatchSearchResponse;
+use lib::handlers::responses::problem_details::ProblemDetails;
 
-use crate::containers::{check_post, no_output_check_post};
+use crate::containers::{check_post, check_post_with_headers, response_get_with_headers};
 
 #[tokio::test]
 #[rstest]
@@ -23,9 +26,79 @@ async fn should_returns_successfully(#[case] keywords: String, #[case] entity: &
 
 #[tokio::test]
 #[rstest]
-#[case("test", "INVALID")]
-async fn should_returns_bad_request(#[case] keywords: String, #[case] entity: &str) -> anyhow::Result<()> {
+#[case("test", "INVALID", "UNKNOWN_TYPE", Some("type"))]
+#[case("   ", "MOVIE", "EMPTY_QUERY", Some("keyword"))]
+#[case("queen (", "MOVIE", "INVALID_QUERY", Some("keyword"))]
+async fn should_returns_bad_request(#[case] keywords: String, #[case] entity: &str, #[case] code: &str, #[case] field: Option<&str>) -> anyhow::Result<()> {
     let request = SearchRequest::new(keywords, Cow::from(entity));
-    no_output_check_post("/run", &request, StatusCode::BAD_REQUEST).await?;
+    let response: ProblemDetails = check_post("/run", &request, StatusCode::BAD_REQUEST).await?;
+    assert_eq!(code, response.code());
+    assert_eq!(field, response.field());
+    assert_eq!(400, response.status());
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn should_returns_bad_request_on_invalid_language() -> anyhow::Result<()> {
+    let request = SearchRequest::new("queen".to_string(), Cow::from("MOVIE"));
+    let response: ProblemDetails = check_post_with_headers("/run", &request, &[("Language", "FR")], StatusCode::BAD_REQUEST).await?;
+    assert_eq!("INVALID_LANGUAGE", response.code());
+    assert_eq!(Some("Language"), response.field());
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_returns_batch_results_in_order() -> anyhow::Result<()> {
+    let requests = vec![
+        BatchSearchRequest::new("queen".to_string(), "MOVIE".to_string(), None, None),
+        BatchSearchRequest::new("command".to_string(), "GAME".to_string(), Some("EN".to_string()), Some(2)),
+        BatchSearchRequest::new("test".to_string(), "INVALID".to_string(), None, None),
+        BatchSearchRequest::new("low".to_string(), "RECIPE".to_string(), None, None)
+            .with_filter("year", serde_json::json!(2020)),
+    ];
+    let response: Vec<BatchSearchResponse> = check_post("/run/batch", &requests, StatusCode::OK).await?;
+
+    assert_eq!(4, response.len());
+    assert_eq!(Some(1), response[0].results().map(<[u64]>::len));
+    assert_eq!(Some(2), response[1].results().map(<[u64]>::len));
+    assert_eq!(400, response[2].status());
+    assert_eq!(Some("UNKNOWN_TYPE"), response[2].error().map(ProblemDetails::code));
+    assert_eq!(Some("UNSUPPORTED_FILTER"), response[3].error().map(ProblemDetails::code));
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_rejects_too_large_batch() -> anyhow::Result<()> {
+    let requests = (0..26)
+        .map(|_| BatchSearchRequest::new("queen".to_string(), "MOVIE".to_string(), None, None))
+        .collect::<Vec<_>>();
+    let response: ProblemDetails = check_post("/run/batch", &requests, StatusCode::BAD_REQUEST).await?;
+    assert_eq!("BATCH_TOO_LARGE", response.code());
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_returns_cacheable_get_search_and_not_modified() -> anyhow::Result<()> {
+    let response = response_get_with_headers("/search?q=queen&type=MOVIE&lang=EN", &[], StatusCode::OK).await?;
+    let etag = response.headers().get("ETag").and_then(|value| value.to_str().ok()).unwrap_or_default().to_string();
+    assert!(etag.starts_with("\"movie-en-"));
+    assert_eq!(Some("public, max-age=60"), response.headers().get("Cache-Control").and_then(|value| value.to_str().ok()));
+    // Without authentication, responses do not vary with the client
+    assert_eq!(Some("Language, Accept-Language"), response.headers().get("Vary").and_then(|value| value.to_str().ok()));
+    assert_eq!(1, response.json::<Vec<u64>>().await?.len());
+
+    let response = response_get_with_headers("/search?q=queen&type=MOVIE&lang=EN", &[("If-None-Match", &etag)], StatusCode::NOT_MODIFIED).await?;
+    assert_eq!(Some(etag.as_str()), response.headers().get("ETag").and_then(|value| value.to_str().ok()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_negotiates_language_from_body_and_accept_language() -> anyhow::Result<()> {
+    let request = SearchRequest::new("queen".to_string(), Cow::from("MOVIE")).with_lang(Cow::from("en-US"));
+    let response: Vec<u64> = check_post_with_headers("/run", &request, &[("Accept-Language", "es")], StatusCode::OK).await?;
+    assert_eq!(1, response.len());
+
+    let response = response_get_with_headers("/search?q=queen&type=MOVIE", &[("Accept-Language", "fr-FR, en-GB;q=0.8")], StatusCode::OK).await?;
+    assert_eq!(Some("en"), response.headers().get("Content-Language").and_then(|value| value.to_str().ok()));
+    Ok(())
+}
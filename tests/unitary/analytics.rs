@@ -0,0 +1,94 @@
+use std::fs;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use lib::handlers::auth_middleware::API_KEY_HEADER;
+use lib::handlers::responses::problem_details::ProblemDetails;
+use lib::infrastructure::di_container::ANALYTICS_SERVICE_IMPL_DEP;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::models::search_event::SearchEvent;
+use lib::services::analytics_service_impl::AnalyticsService;
+use lib::services::api_key_service_impl::ApiKeyServiceImpl;
+use lib::services::impls::file_search_event_sink::FileSearchEventSink;
+use lib::services::search_event_sink::SearchEventSink;
+
+use crate::{serve, Fixture};
+
+fn event(keywords: &str, ids: &[u64]) -> SearchEvent {
+    SearchEvent::new(Entity::Movie, Language::En, keywords, ids, 2, Duration::from_millis(3), None)
+}
+
+#[tokio::test]
+async fn should_reports_top_and_zero_result_queries() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.container(&format!(r#"
+[analytics]
+enabled = true
+top_ids = 2
+sink = {{ kind = "file", path = "{}" }}
+"#, fixture.path("events.jsonl").display()))?;
+    let analytics = di_container.get(ANALYTICS_SERVICE_IMPL_DEP)?;
+    analytics.start();
+    analytics.record(event("The  Matrix", &[1, 2, 3]));
+    analytics.record(event("the matrix", &[1, 2, 3]));
+    analytics.record(event("inception", &[4]));
+    analytics.record(event("xyzzy", &[]));
+    analytics.stop(Duration::from_secs(5)).await;
+
+    let line = fs::read_to_string(fixture.path("events.jsonl"))?.lines().next().unwrap_or_default().to_string();
+    assert!(line.contains(r#""query":"the matrix""#));
+    assert!(line.contains(r#""top_ids":[1,2]"#));
+
+    let report = analytics.report(Duration::from_secs(3600), 2).await?;
+    assert_eq!(vec![("the matrix", 2), ("inception", 1)],
+               report.top_queries().iter().map(|count| (count.query(), count.count())).collect::<Vec<_>>());
+    assert_eq!(vec!["xyzzy"], report.top_zero_result_queries().iter().map(|count| count.query()).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_rotates_event_files() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let path = fixture.path("events.jsonl");
+    let sink = FileSearchEventSink::new(path.clone(), 1, 2);
+
+    for keywords in ["first", "second", "third"] {
+        sink.write(&[event(keywords, &[1])]).await?;
+    }
+
+    assert!(!path.exists());
+    assert!(fs::read_to_string(fixture.path("events.jsonl.1"))?.contains("third"));
+    assert!(fs::read_to_string(fixture.path("events.jsonl.2"))?.contains("second"));
+    assert!(!fixture.path("events.jsonl.3").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_report_disabled_analytics_as_not_found() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.container(&format!(r#"
+[server]
+host = "127.0.0.1"
+port = 0
+
+[auth]
+enabled = true
+
+[[auth.keys]]
+name = "ops"
+key_sha256 = "{}"
+scopes = ["admin"]
+"#, ApiKeyServiceImpl::hash_key("ops-secret")))?;
+    let (base_url, shutdown) = serve(&di_container).await?;
+
+    let response = reqwest::Client::new().get(format!("{base_url}/admin/analytics"))
+        .header(API_KEY_HEADER, "ops-secret")
+        .send().await?;
+    shutdown.shutdown();
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+    assert_eq!("ANALYTICS_DISABLED", response.json::<ProblemDetails>().await?.code());
+    Ok(())
+}
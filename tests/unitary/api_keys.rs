@@ -0,0 +1,165 @@
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+
+use lib::config::auth_config::Scope;
+use lib::config::ConfigError;
+use lib::infrastructure::di_container::API_KEY_SERVICE_IMPL_DEP;
+use lib::models::auth_error::AuthError;
+use lib::models::entity::Entity;
+use lib::services::api_key_service_impl::{ApiKeyService, ApiKeyServiceImpl};
+use lib::services::rate_limiter::{QuotaWindow, TokenBucket};
+
+use crate::{serve, Fixture};
+
+#[test]
+fn should_refills_token_bucket_over_time() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(2, 4.0, start);
+    for _ in 0..2 {
+        assert_eq!(None, bucket.retry_after(start));
+        bucket.take();
+    }
+
+    assert_eq!(Some(Duration::from_millis(250)), bucket.retry_after(start));
+    assert_eq!(None, bucket.retry_after(start + Duration::from_millis(250)));
+    // Idle time never grows the bucket past its capacity
+    assert_eq!(None, bucket.retry_after(start + Duration::from_secs(60)));
+    assert_eq!(2.0, bucket.tokens());
+}
+
+#[test]
+fn should_resets_quota_once_period_ends() {
+    let start = Instant::now();
+    let mut quota = QuotaWindow::new(1, Duration::from_secs(60));
+    assert_eq!(None, quota.retry_after(start));
+    quota.take();
+
+    assert_eq!(Some(Duration::from_secs(50)), quota.retry_after(start + Duration::from_secs(10)));
+    assert_eq!(None, quota.retry_after(start + Duration::from_secs(60)));
+    assert_eq!(1, quota.remaining());
+}
+
+#[tokio::test]
+async fn should_authorizes_hashed_keys_by_scope_and_limits() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.container(&format!(r#"
+[auth]
+enabled = true
+
+[[auth.keys]]
+name = "partner"
+key_sha256 = "{}"
+rate_limit = {{ per_second = 0.01, burst = 2 }}
+
+[[auth.keys]]
+name = "ops"
+key_sha256 = "{}"
+scopes = ["admin"]
+quota = {{ requests = 1, period = 3600 }}
+
+[sources.movie]
+kind = "memory"
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, ApiKeyServiceImpl::hash_key("partner-secret"), ApiKeyServiceImpl::hash_key("ops-secret").to_ascii_uppercase()))?;
+    let api_key_service = di_container.get(API_KEY_SERVICE_IMPL_DEP)?;
+
+    assert!(matches!(api_key_service.authorize(None, Scope::Search), Err(AuthError::MissingKey)));
+    assert!(matches!(api_key_service.authorize(Some("guess"), Scope::Search), Err(AuthError::InvalidKey)));
+    assert!(matches!(api_key_service.authorize(Some("partner-secret"), Scope::Admin), Err(AuthError::Forbidden(..))));
+
+    assert_eq!("partner", api_key_service.authorize(Some("partner-secret"), Scope::Search)?);
+    assert_eq!("partner", api_key_service.authorize(Some("partner-secret"), Scope::Search)?);
+    let Err(err) = api_key_service.authorize(Some("partner-secret"), Scope::Search) else {
+        panic!("expected the burst to be exhausted");
+    };
+    assert!(matches!(err, AuthError::RateLimited(..)));
+    assert!(err.retry_after().is_some_and(|retry_after| retry_after > Duration::from_secs(90)));
+
+    // Admin keys may search too
+    assert_eq!("ops", api_key_service.authorize(Some("ops-secret"), Scope::Search)?);
+    assert!(matches!(api_key_service.authorize(Some("ops-secret"), Scope::Admin), Err(AuthError::QuotaExceeded(..))));
+
+    let usage = api_key_service.usage();
+    assert_eq!(vec!["ops", "partner"], usage.iter().map(|client| client.name()).collect::<Vec<_>>());
+    assert_eq!(Some(0), usage[0].quota_remaining());
+    Ok(())
+}
+
+#[test]
+fn should_rejects_invalid_api_keys() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let result = fixture.config(r#"
+[auth]
+enabled = true
+
+[[auth.keys]]
+name = "partner"
+key_sha256 = "not-a-digest"
+scopes = []
+rate_limit = { per_second = 0.0, burst = 1 }
+"#);
+
+    let Err(ConfigError::Invalid(errors)) = result else {
+        panic!("expected validation errors");
+    };
+    assert_eq!(3, errors.len());
+    assert!(errors.iter().any(|error| error.contains("key_sha256")));
+    assert!(errors.iter().any(|error| error.contains("scopes")));
+    assert!(errors.iter().any(|error| error.contains("rate_limit")));
+    Ok(())
+}
+
+
+#[tokio::test]
+async fn should_refuses_admin_routes_while_auth_is_disabled() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.indexed_container(&format!(r#"
+[server]
+host = "127.0.0.1"
+port = 0
+
+[auth]
+enabled = false
+
+[blocklist]
+enabled = true
+store = {{ kind = "file", path = "{}" }}
+
+[sources.movie]
+kind = "memory"
+documents = [{{ id = 1, language = "EN", title = "The Matrix" }}]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, fixture.path("blocklist.json").display()), &[Entity::Movie]).await?;
+    let (base_url, shutdown) = serve(&di_container).await?;
+    let client = reqwest::Client::new();
+
+    let clients = client.get(format!("{base_url}/admin/clients")).send().await?;
+    let blocklist = client.get(format!("{base_url}/admin/blocklist")).send().await?;
+    let unblock = client.delete(format!("{base_url}/admin/blocklist/MOVIE/1")).send().await?;
+    let search = client.get(format!("{base_url}/search?q=matrix&type=MOVIE")).send().await?;
+    shutdown.shutdown();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, clients.status());
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, blocklist.status());
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, unblock.status());
+    // Searches stay open without keys
+    assert_eq!(StatusCode::OK, search.status());
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use lib::handlers::responses::problem_details::ProblemDetails;
+use lib::infrastructure::di_container::{BLOCKLIST_SERVICE_IMPL_DEP, DIContainer, MOVIE_INDEX_PROCESSOR_DEP, SEARCH_SERVICE_IMPL_DEP};
+use lib::infrastructure::shutdown::ShutdownHandle;
+use lib::models::blocked_id::BlockedId;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::models::search_error::SearchError;
+use lib::services::blocklist_service_impl::BlocklistService;
+use lib::services::index_processor::IndexSearcher;
+use lib::services::indexer_runner::IndexerRunner;
+use lib::services::search_service_impl::SearchService;
+
+use crate::Fixture;
+
+fn container(fixture: &Fixture, blocklist_path: Option<&str>) -> anyhow::Result<Arc<DIContainer>> {
+    let blocklist = blocklist_path
+        .map(|path| format!("[blocklist]\nenabled = true\nstore = {{ kind = \"file\", path = \"{path}\" }}"))
+        .unwrap_or_default();
+    fixture.container(&format!(r#"
+[search_cache]
+enabled = true
+max_entries = 100
+
+{blocklist}
+
+[sources.movie]
+kind = "memory"
+documents = [{{ id = 1, language = "EN", title = "The Matrix" }}, {{ id = 2, language = "EN", title = "The Matrix Reloaded" }}, {{ id = 3, language = "ES", title = "Matrix Recargado" }}]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#))
+}
+
+#[tokio::test]
+async fn should_removes_blocked_ids_from_live_and_rebuilt_indexes() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let blocklist_path = fixture.path("blocklist.json");
+
+    let di_container = container(&fixture, blocklist_path.to_str())?;
+    let indexer_runner = IndexerRunner::new(ShutdownHandle::new());
+    indexer_runner.run_once(&di_container, &[Entity::Movie]).await?;
+    let search_service = di_container.get(SEARCH_SERVICE_IMPL_DEP)?;
+    let index_processor = di_container.get(MOVIE_INDEX_PROCESSOR_DEP)?;
+    let blocklist = di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?;
+    let search = |language| search_service.search(&mut "matrix".to_string(), language, Entity::Movie);
+
+    assert_eq!(vec![1, 2], search(Language::En)?);
+
+    let changes = Arc::new(AtomicUsize::new(0));
+    let counter = changes.clone();
+    index_processor.on_change(move |language| {
+        if language == Language::En {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    let blocked = blocklist.block(Entity::Movie, &[2, 3], Some("takedown".to_string())).await?;
+    assert_eq!(2, blocked.len());
+    // One delete per language, the reload following the block never deletes the same ids again
+    assert_eq!(1, changes.load(Ordering::SeqCst));
+    assert_eq!(vec![1], search(Language::En)?);
+    assert!(search(Language::Es)?.is_empty());
+    // Deleted from the live index, not only filtered
+    assert_eq!(vec![1], index_processor.search(Language::En, &["matrix"])?);
+
+    indexer_runner.run_once(&di_container, &[Entity::Movie]).await?;
+    assert_eq!(vec![1], index_processor.search(Language::En, &["matrix"])?);
+
+    // Blocked ids survive restarts
+    let restarted = container(&fixture, blocklist_path.to_str())?;
+    let restarted_blocklist = restarted.get(BLOCKLIST_SERVICE_IMPL_DEP)?;
+    restarted_blocklist.reload().await?;
+    assert_eq!(Some("takedown"), restarted_blocklist.entries()[0].reason());
+
+    let reblocked = blocklist.block(Entity::Movie, &[2, 4], Some("mistake".to_string())).await?;
+    let entry = |entries: &[BlockedId], id| entries.iter().find(|entry| entry.id() == id).cloned();
+    assert_eq!(2, reblocked.len());
+    // Already blocked ids answer their stored entry
+    assert_eq!(Some("takedown"), entry(&reblocked, 2).as_ref().and_then(BlockedId::reason));
+    assert_eq!(entry(&blocked, 2).map(|entry| entry.blocked_at()), entry(&reblocked, 2).map(|entry| entry.blocked_at()));
+    assert_eq!(Some("mistake"), entry(&reblocked, 4).as_ref().and_then(BlockedId::reason));
+
+    assert!(blocklist.unblock(Entity::Movie, 2).await?);
+    assert!(!blocklist.unblock(Entity::Movie, 2).await?);
+    assert_eq!(vec![1], search(Language::En)?);
+    indexer_runner.run_once(&di_container, &[Entity::Movie]).await?;
+    assert_eq!(vec![1, 2], search(Language::En)?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_report_a_disabled_blocklist_as_not_found() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture, None)?;
+    let blocklist = di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?;
+
+    let err = blocklist.block(Entity::Movie, &[1], None).await.err();
+    assert!(matches!(err, Some(SearchError::BlocklistDisabled)));
+    let problem = ProblemDetails::from(&err.unwrap());
+    assert_eq!(404, problem.status());
+    assert_eq!("BLOCKLIST_DISABLED", problem.code());
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use lib::infrastructure::di_container::{CHANGE_FEED_SERVICE_IMPL_DEP, DIContainer, DOCUMENT_SERVICE_IMPL_DEP, MOVIE_INDEX_PROCESSOR_DEP};
+use lib::infrastructure::shutdown::ShutdownHandle;
+use lib::models::doc_details::DocDetails;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::services::change_feed_service_impl::ChangeFeedService;
+use lib::services::document_service_impl::DocumentService;
+use lib::services::indexer_runner::IndexerRunner;
+
+use tokio::time::{Duration, Instant};
+
+use crate::Fixture;
+
+async fn container(fixture: &Fixture) -> anyhow::Result<Arc<DIContainer>> {
+    fixture.indexed_container(r#"
+[change_feed]
+enabled = true
+
+[sources.movie]
+kind = "memory"
+documents = [{ id = 1, language = "EN", title = "The Matrix" }, { id = 2, language = "EN", title = "The Matrix Reloaded" }]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, &[Entity::Movie]).await
+}
+
+fn search(di_container: &DIContainer, keywords: &str) -> anyhow::Result<Vec<u64>> {
+    crate::search(di_container, keywords, Language::En, Entity::Movie)
+}
+
+#[tokio::test]
+async fn should_applies_change_events_from_the_data_source() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let change_feed = di_container.get(CHANGE_FEED_SERVICE_IMPL_DEP)?;
+    let documents = di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?;
+    documents.delete(Entity::Movie, Language::En, &[1])?;
+    documents.upsert(Entity::Movie, Language::En, vec![DocDetails::new(3, "The Matrix Revolutions".to_string())])?;
+    assert_eq!(vec![2, 3], search(&di_container, "matrix")?);
+
+    // Ids still in the data source are upserted with its content, missing ones are deleted
+    change_feed.apply(r#"{"type": "MOVIE", "id": 1, "language": "EN"}"#).await?;
+    change_feed.apply(r#"{"type": "movie", "id": 3, "language": "en"}"#).await?;
+
+    let mut found = search(&di_container, "matrix")?;
+    found.sort();
+    assert_eq!(vec![1, 2], found);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_applies_a_burst_of_change_events_in_one_write_per_index() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let change_feed = di_container.get(CHANGE_FEED_SERVICE_IMPL_DEP)?;
+    let documents = di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?;
+    let index_processor = di_container.get(MOVIE_INDEX_PROCESSOR_DEP)?;
+    documents.delete(Entity::Movie, Language::En, &[1])?;
+    documents.upsert(Entity::Movie, Language::En, vec![DocDetails::new(3, "The Matrix Revolutions".to_string())])?;
+
+    let changes = Arc::new(AtomicUsize::new(0));
+    let counter = changes.clone();
+    index_processor.on_change(move |language| {
+        if language == Language::En {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    let payloads = [
+        r#"{"type": "MOVIE", "id": 1, "language": "EN"}"#,
+        r#"{"type": "MOVIE", "id": 2, "language": "EN"}"#,
+        r#"{"type": "BOOK", "id": 1, "language": "EN"}"#,
+        r#"{"type": "MOVIE", "id": 3, "language": "EN"}"#,
+        r#"{"type": "MOVIE", "id": 1, "language": "EN"}"#,
+    ].map(str::to_string);
+    change_feed.apply_batch(&payloads).await?;
+
+    // A single upsert and a single delete, the invalid event is skipped
+    assert_eq!(2, changes.load(Ordering::SeqCst));
+    let mut found = search(&di_container, "matrix")?;
+    found.sort();
+    assert_eq!(vec![1, 2], found);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_rejects_invalid_change_events() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let change_feed = di_container.get(CHANGE_FEED_SERVICE_IMPL_DEP)?;
+
+    assert!(change_feed.enabled());
+    assert!(change_feed.apply("42").await.is_err());
+    assert!(change_feed.apply(r#"{"type": "BOOK", "id": 1, "language": "EN"}"#).await.is_err());
+    assert!(change_feed.apply(r#"{"type": "MOVIE", "id": 1, "language": "FR"}"#).await.is_err());
+    assert_eq!(vec![1, 2], search(&di_container, "matrix")?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_waits_for_in_progress_rebuilds_before_catching_up() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let documents = di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?;
+    documents.delete(Entity::Movie, Language::En, &[1])?;
+
+    // Rebuilds of the same entity are serialized, both complete with the data source content
+    let indexer_runner = IndexerRunner::new(ShutdownHandle::new());
+    let (first, second) = tokio::join!(
+        indexer_runner.run_once(&di_container, &[Entity::Movie]),
+        indexer_runner.run_once(&di_container, &[Entity::Movie]));
+    first?;
+    second?;
+
+    let mut found = search(&di_container, "matrix")?;
+    found.sort();
+    assert_eq!(vec![1, 2], found);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn should_rate_limits_catch_up_rebuilds() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let change_feed = di_container.get(CHANGE_FEED_SERVICE_IMPL_DEP)?;
+    let documents = di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?;
+    let indexer_runner = IndexerRunner::new(ShutdownHandle::new());
+
+    change_feed.catch_up(&indexer_runner).await;
+    documents.delete(Entity::Movie, Language::En, &[1])?;
+
+    // A flapping connection waits for the catch up interval, then rebuilds with the data source content
+    let started = Instant::now();
+    change_feed.catch_up(&indexer_runner).await;
+    assert!(started.elapsed() >= Duration::from_secs(300));
+    let mut found = search(&di_container, "matrix")?;
+    found.sort();
+    assert_eq!(vec![1, 2], found);
+    Ok(())
+}
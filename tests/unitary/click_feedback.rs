@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::StatusCode;
+
+use lib::handlers::requests::click_feedback_request::ClickFeedbackRequest;
+use lib::handlers::responses::problem_details::ProblemDetails;
+use lib::infrastructure::di_container::{DIContainer, FEEDBACK_SERVICE_IMPL_DEP, SEARCH_SERVICE_IMPL_DEP};
+use lib::models::click_aggregate::DecayedCount;
+use lib::models::doc_details::DocDetails;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::models::search_error::SearchError;
+use lib::services::feedback_service_impl::FeedbackService;
+use lib::services::index_processor::{IndexProcessor, IndexSearcher, IndexWriter, LIMIT_RESULT_SIZE};
+use lib::services::search_service_impl::SearchService;
+
+use crate::{serve, Fixture};
+
+async fn container(fixture: &Fixture) -> anyhow::Result<Arc<DIContainer>> {
+    container_with(fixture, "").await
+}
+
+async fn container_with(fixture: &Fixture, feedback: &str) -> anyhow::Result<Arc<DIContainer>> {
+    fixture.indexed_container(&format!(r#"
+[server]
+host = "127.0.0.1"
+port = 0
+
+[search_cache]
+enabled = true
+max_entries = 100
+
+[feedback]
+enabled = true
+path = "{}"
+{feedback}
+
+[sources.movie]
+kind = "memory"
+documents = [{{ id = 1, language = "EN", title = "The Matrix" }}, {{ id = 2, language = "EN", title = "The Matrix Reloaded" }}, {{ id = 3, language = "EN", title = "The Matrix Revolutions" }}]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, fixture.path("click-feedback.json").display()), &[Entity::Movie]).await
+}
+
+fn search(di_container: &DIContainer, keywords: &str) -> anyhow::Result<Vec<u64>> {
+    crate::search(di_container, keywords, Language::En, Entity::Movie)
+}
+
+#[tokio::test]
+async fn should_boosts_clicked_results_across_restarts() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    assert_eq!(1, search(&di_container, "matrix")?[0]);
+    assert_eq!(1, search(&di_container, "the matrix")?[0]);
+
+    let feedback = di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?;
+    let search_service = di_container.get(SEARCH_SERVICE_IMPL_DEP)?;
+    assert_eq!(0, search_service.feedback_version("the matrix", Language::En, Entity::Movie));
+    for _ in 0..20 {
+        feedback.record_click(Entity::Movie, Language::En, "The  MATRIX", 3)?;
+    }
+    // The cached results of the clicked query are invalidated, other queries are not boosted
+    assert_eq!(3, search(&di_container, "the matrix")?[0]);
+    assert_eq!(1, search(&di_container, "matrix")?[0]);
+    assert!(feedback.boosts(Entity::Movie, Language::Es, "the matrix").is_empty());
+    // Conditional GET requests of the clicked query only are revalidated
+    assert_eq!(20, search_service.feedback_version("The Matrix", Language::En, Entity::Movie));
+    assert_eq!(0, search_service.feedback_version("matrix", Language::En, Entity::Movie));
+
+    feedback.save()?;
+    let restarted = container(&fixture).await?;
+    assert_eq!(3, search(&restarted, "the matrix")?[0]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_rejects_clicks_on_unknown_ids_and_bounds_clicked_queries() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container_with(&fixture, "max_queries = 2").await?;
+    let feedback = di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?;
+
+    let err = feedback.record_click(Entity::Movie, Language::En, "matrix", 42).err();
+    assert!(matches!(err, Some(SearchError::UnknownDocument(42))));
+    // Ids are looked up in the index of the clicked language
+    assert!(feedback.record_click(Entity::Movie, Language::Es, "matrix", 1).is_err());
+    assert_eq!(0, feedback.query_count());
+
+    for query in ["matrix", "the matrix", "matrix reloaded", "matrix revolutions"] {
+        feedback.record_click(Entity::Movie, Language::En, query, 2)?;
+    }
+    assert_eq!(2, feedback.query_count());
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_accepts_clicks_whatever_the_case_of_the_type() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let (base_url, shutdown) = serve(&di_container).await?;
+    let client = reqwest::Client::new();
+
+    let mut statuses = Vec::new();
+    for entity in ["MOVIE", "movie", "Movie"] {
+        let request = ClickFeedbackRequest::new("matrix".to_string(), entity.to_string(), Some("EN".to_string()), 2);
+        statuses.push(client.post(format!("{base_url}/feedback/click")).json(&request).send().await?.status());
+    }
+    shutdown.shutdown();
+
+    assert_eq!(vec![StatusCode::ACCEPTED; 3], statuses);
+    assert_eq!(3, di_container.get(SEARCH_SERVICE_IMPL_DEP)?.feedback_version("matrix", Language::En, Entity::Movie));
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_report_disabled_feedback_as_not_found() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.container(r#"
+[sources.movie]
+kind = "memory"
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#)?;
+    let feedback = di_container.get(FEEDBACK_SERVICE_IMPL_DEP)?;
+
+    let err = feedback.record_click(Entity::Movie, Language::En, "matrix", 1).err();
+    assert!(matches!(err, Some(SearchError::FeedbackDisabled)));
+    let problem = ProblemDetails::from(&err.unwrap());
+    assert_eq!(404, problem.status());
+    assert_eq!("FEEDBACK_DISABLED", problem.code());
+    Ok(())
+}
+
+#[test]
+fn should_boosts_hits_beyond_the_unboosted_top() -> anyhow::Result<()> {
+    let processor = IndexProcessor::new()?;
+    let documents = (1..=LIMIT_RESULT_SIZE as u64 + 10).map(|id| DocDetails::new(id, "Matrix".to_string())).collect::<Vec<_>>();
+    processor.swap_index(Language::En, &documents)?;
+
+    let unboosted = processor.search(Language::En, &["matrix"])?;
+    let missing = documents.iter().map(DocDetails::id).find(|id| !unboosted.contains(id)).unwrap_or_default();
+    let boosted = processor.search_boosted(Language::En, &["matrix"], &HashMap::from([(missing, 2.0)]))?;
+
+    assert_eq!(Some(&missing), boosted.first());
+    assert_eq!(LIMIT_RESULT_SIZE, boosted.len());
+    Ok(())
+}
+
+#[test]
+fn should_halves_clicks_every_half_life() {
+    let half_life = Duration::from_secs(3600);
+    let now = Utc::now();
+    let mut count = DecayedCount::new(0.0, now - chrono::Duration::hours(2));
+    count.add(8.0, now - chrono::Duration::hours(2), half_life);
+
+    assert!((count.weight_at(now, half_life) - 2.0).abs() < 1e-6);
+
+    count.add(1.0, now, half_life);
+    assert!((count.weight_at(now + chrono::Duration::hours(1), half_life) - 1.5).abs() < 1e-6);
+}
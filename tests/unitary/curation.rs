@@ -0,0 +1,78 @@
+use rstest::rstest;
+
+use lib::infrastructure::di_container::{CURATION_SERVICE_IMPL_DEP, SEARCH_SERVICE_IMPL_DEP};
+use lib::models::curation_rule::{CurationAction, CurationRule};
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::models::search_error::SearchError;
+use lib::services::curation_service_impl::CurationService;
+use lib::services::search_service_impl::SearchService;
+
+use crate::Fixture;
+
+#[rstest]
+#[case(CurationAction::Pin { ids: vec![9, 3] }, vec![9, 3, 1, 2, 4])]
+#[case(CurationAction::Hide { ids: vec![2, 9] }, vec![1, 3, 4])]
+#[case(CurationAction::Boost { ids: vec![4], positions: 2 }, vec![1, 4, 2, 3])]
+#[case(CurationAction::Boost { ids: vec![2, 9], positions: 5 }, vec![2, 1, 3, 4])]
+fn should_applies_curation_action(#[case] action: CurationAction, #[case] expected: Vec<u64>) {
+    let mut results = vec![1, 2, 3, 4];
+    action.apply(&mut results);
+    assert_eq!(expected, results);
+}
+
+#[tokio::test]
+async fn should_curates_search_results_from_file_rules() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.indexed_container(&format!(r#"
+[search_cache]
+enabled = true
+max_entries = 100
+
+[curation]
+enabled = true
+store = {{ kind = "file", path = "{}" }}
+
+[sources.movie]
+kind = "memory"
+documents = [{{ id = 1, language = "EN", title = "The Matrix" }}, {{ id = 2, language = "EN", title = "The Matrix Reloaded" }}, {{ id = 3, language = "ES", title = "Matrix" }}]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, fixture.path("rules.json").display()), &[Entity::Movie]).await?;
+    let search_service = di_container.get(SEARCH_SERVICE_IMPL_DEP)?;
+    let curation = di_container.get(CURATION_SERVICE_IMPL_DEP)?;
+    let search = |keywords: &str, language| search_service.search(&mut keywords.to_string(), language, Entity::Movie);
+
+    assert_eq!(vec![1, 2], search("matrix", Language::En)?);
+    assert_eq!(0, search_service.rules_fingerprint());
+
+    let pin = curation.save(CurationRule::new("pin".to_string(), " The MATRIX ".to_string(), "movie".to_string(), Some("en".to_string()), CurationAction::Pin { ids: vec![7] })).await?;
+    assert_eq!(("the matrix", "MOVIE", Some("EN")), (pin.query(), pin.entity(), pin.language()));
+    curation.save(CurationRule::new("takedown".to_string(), "the matrix".to_string(), "MOVIE".to_string(), None, CurationAction::Hide { ids: vec![1] })).await?;
+
+    assert_eq!(vec![7, 2], search("The Matrix", Language::En)?);
+    assert_eq!(vec![1, 2], search("matrix", Language::En)?);
+    assert!(search("the matrix", Language::Es)?.is_empty());
+    assert_ne!(0, search_service.rules_fingerprint());
+
+    // Rules edited by hand are picked up by the next reload
+    fixture.write("rules.json", r#"[{"id": "boost", "query": "matrix", "entity": "MOVIE", "action": "boost", "ids": [2], "positions": 1}]"#)?;
+    assert!(curation.reload().await?);
+    assert_eq!(vec![2, 1], search("matrix", Language::En)?);
+    assert_eq!(vec![1, 2], search("the matrix", Language::En)?);
+
+    assert!(curation.delete("boost").await?);
+    assert!(!curation.delete("boost").await?);
+    assert!(curation.rules().is_empty());
+    assert!(matches!(
+        curation.save(CurationRule::new("empty".to_string(), "matrix".to_string(), "MOVIE".to_string(), None, CurationAction::Hide { ids: vec![] })).await,
+        Err(SearchError::InvalidRule(_))));
+    Ok(())
+}
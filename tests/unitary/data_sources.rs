@@ -0,0 +1,74 @@
+use lib::config::ConfigError;
+use lib::infrastructure::di_container::index_processor_dep;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::services::index_processor::IndexSearcher;
+
+use crate::Fixture;
+
+#[tokio::test]
+async fn should_indexes_without_database() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let movies = fixture.write("movies.jsonl", r#"{"id": 1, "language": "EN", "title": "The Matrix"}
+{"id": 2, "language": "ES", "title": "Matrix Recargado"}
+
+{"id": 3, "language": "EN", "title": "Inception"}
+"#)?;
+    let tv = fixture.write("tv.csv", "id,language,title\n10,EN,\"Dark, the series\"\n11,EN,Severance\n")?;
+    let content = format!(r#"
+[sources.movie]
+kind = "file"
+path = "{}"
+
+[sources.tv]
+kind = "file"
+path = "{}"
+
+[sources.recipe]
+kind = "memory"
+documents = [{{ id = 20, language = "EN", title = "Pancakes" }}]
+
+[sources.game]
+kind = "memory"
+"#, movies.display(), tv.display());
+
+    assert!(!fixture.config(&content)?.uses_database());
+    let di_container = fixture.indexed_container(&content, &Entity::all()).await?;
+
+    let search = |entity: Entity, language: Language, token: &str| -> anyhow::Result<Vec<u64>> {
+        Ok(di_container.get(index_processor_dep(entity))?.search(language, &[token])?)
+    };
+    assert_eq!(vec![1], search(Entity::Movie, Language::En, "matrix")?);
+    assert_eq!(vec![2], search(Entity::Movie, Language::Es, "matrix")?);
+    assert_eq!(vec![10], search(Entity::Tv, Language::En, "dark")?);
+    assert_eq!(vec![20], search(Entity::Recipe, Language::En, "pan")?);
+    assert!(search(Entity::Game, Language::En, "chess")?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn should_rejects_invalid_data_sources() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let result = fixture.config(r#"
+[sources.podcast]
+kind = "database"
+
+[sources.movie]
+kind = "file"
+path = "/nonexistent/movies.txt"
+
+[sources.recipe]
+kind = "memory"
+documents = [{ id = 1, language = "FR", title = "Crêpes" }]
+"#);
+
+    let Err(ConfigError::Invalid(errors)) = result else {
+        panic!("expected validation errors");
+    };
+    assert_eq!(4, errors.len());
+    assert!(errors.iter().any(|error| error.contains("sources.podcast")));
+    assert!(errors.iter().any(|error| error.contains("is not a file")));
+    assert!(errors.iter().any(|error| error.contains("sources.movie.format")));
+    assert!(errors.iter().any(|error| error.contains("'FR'")));
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use lib::models::doc_details::DocDetails;
+use lib::models::language::Language;
+use lib::services::index_processor::{IndexProcessor, IndexSearcher, IndexWriter};
+
+#[test]
+fn should_keeps_the_last_document_of_duplicated_ids() -> anyhow::Result<()> {
+    let processor = IndexProcessor::new()?;
+    processor.swap_index(Language::En, &[
+        DocDetails::new(1, "The Matrix".to_string()),
+        DocDetails::new(2, "Matrix Reloaded".to_string()),
+        DocDetails::new(1, "The Matrix Revolutions".to_string()),
+    ])?;
+
+    assert_eq!(vec![2, 1], processor.search(Language::En, &["matrix"])?);
+    assert_eq!(vec![1], processor.search(Language::En, &["revolutions"])?);
+    Ok(())
+}
+
+#[test]
+fn should_replaces_indexed_ids_on_later_writes() -> anyhow::Result<()> {
+    let processor = IndexProcessor::new()?;
+    processor.write_all(Language::En, &[DocDetails::new(1, "The Matrix".to_string())])?;
+    processor.write_all(Language::En, &[DocDetails::new(1, "The Matrix Reloaded".to_string())])?;
+
+    assert_eq!(vec![1], processor.search(Language::En, &["the", "matrix"])?);
+    assert_eq!(vec![1], processor.search(Language::En, &["reloaded"])?);
+    Ok(())
+}
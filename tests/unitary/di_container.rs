@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use mockall::mock;
+use mockall::predicate::eq;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use lib::config::Config;
+use lib::entities::movie::Movie;
+use lib::infrastructure::app_runner::AppRunner;
+use lib::infrastructure::di_container::{CONFIG_DEP, DIContainer, DIError, MOVIE_INDEX_PROCESSOR_DEP, MOVIE_REPOSITORY_DEP};
+use lib::infrastructure::shutdown::ShutdownHandle;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::repositories::movie_repository_impl::MovieRepository;
+use lib::services::index_processor::IndexSearcher;
+use lib::services::indexer_runner::IndexerRunner;
+
+mock! {
+    MovieRepo {}
+
+    #[async_trait]
+    impl MovieRepository for MovieRepo {
+        async fn find_movies_by_lang_and_limit_offset(&self, lang: &str, limit: u64, offset: u64) -> anyhow::Result<Vec<Movie>>;
+
+        async fn find_movies_by_lang_and_ids(&self, lang: &str, ids: &[u64]) -> anyhow::Result<Vec<Movie>>;
+    }
+}
+
+#[test]
+fn should_fails_on_missing_or_duplicated_dependency() -> anyhow::Result<()> {
+    let di_container = DIContainer::new();
+    assert!(matches!(di_container.get(CONFIG_DEP), Err(DIError::NotRegistered("config"))));
+
+    di_container.add(CONFIG_DEP, Config::load_from(None)?)?;
+    assert!(di_container.get(CONFIG_DEP).is_ok());
+    assert!(matches!(di_container.add(CONFIG_DEP, Config::load_from(None)?), Err(DIError::AlreadyRegistered("config"))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_indexes_from_overridden_repository() -> anyhow::Result<()> {
+    let mut movie_repository = MockMovieRepo::new();
+    movie_repository.expect_find_movies_by_lang_and_limit_offset()
+        .with(eq("EN"), eq(1000), eq(0))
+        .returning(|_, _, _| Ok(vec![Movie::new(7, "the matrix".to_string())]));
+    movie_repository.expect_find_movies_by_lang_and_limit_offset()
+        .returning(|_, _, _| Ok(Vec::new()));
+
+    let overrides = DIContainer::new();
+    overrides.override_with(MOVIE_REPOSITORY_DEP, Arc::new(movie_repository) as Arc<dyn MovieRepository>);
+    let db_pool = PgPoolOptions::new().connect_lazy_with(PgConnectOptions::new());
+    let di_container = AppRunner::build_container(overrides, Config::load_from(None)?, db_pool)?;
+
+    IndexerRunner::new(ShutdownHandle::new()).run_once(&di_container, &[Entity::Movie]).await?;
+
+    let index_processor = di_container.get(MOVIE_INDEX_PROCESSOR_DEP)?;
+    assert_eq!(vec![7], index_processor.search(Language::En, &["matrix"])?);
+    assert!(index_processor.search(Language::Es, &["matrix"])?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+
+use lib::handlers::requests::document_request::DocumentRequest;
+use lib::infrastructure::di_container::{BLOCKLIST_SERVICE_IMPL_DEP, DIContainer, DOCUMENT_SERVICE_IMPL_DEP};
+use lib::models::doc_details::DocDetails;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::services::blocklist_service_impl::BlocklistService;
+use lib::services::document_service_impl::DocumentService;
+
+use crate::{serve, Fixture};
+
+async fn container(fixture: &Fixture) -> anyhow::Result<Arc<DIContainer>> {
+    fixture.indexed_container(&format!(r#"
+[search_cache]
+enabled = true
+max_entries = 100
+
+[blocklist]
+enabled = true
+store = {{ kind = "file", path = "{}" }}
+
+[sources.movie]
+kind = "memory"
+documents = [{{ id = 1, language = "EN", title = "The Matrix" }}, {{ id = 2, language = "EN", title = "The Matrix Reloaded" }}]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, fixture.path("blocklist.json").display()), &[Entity::Movie]).await
+}
+
+fn search(di_container: &DIContainer, keywords: &str) -> anyhow::Result<Vec<u64>> {
+    crate::search(di_container, keywords, Language::En, Entity::Movie)
+}
+
+#[tokio::test]
+async fn should_replaces_upserted_ids_and_invalidates_cached_results() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let documents = di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?;
+    assert_eq!(vec![1, 2], search(&di_container, "matrix")?);
+    assert!(search(&di_container, "revolutions")?.is_empty());
+
+    let blocked = documents.upsert(Entity::Movie, Language::En, vec![
+        DocDetails::new(2, "The Matrix Revolutions".to_string()),
+        DocDetails::new(3, "Speed".to_string()),
+        DocDetails::new(3, "Speed Racer".to_string()),
+    ])?;
+
+    assert!(blocked.is_empty());
+    assert_eq!(vec![2], search(&di_container, "revolutions")?);
+    assert!(search(&di_container, "reloaded")?.is_empty());
+    // The last document of a repeated id wins
+    assert_eq!(vec![3], search(&di_container, "racer")?);
+    assert_eq!(vec![3], search(&di_container, "speed")?);
+    assert!(search(&di_container, "matrix")?.contains(&2));
+    assert!(documents.upsert(Entity::Movie, Language::Es, vec![DocDetails::new(4, "Matrix".to_string())])?.is_empty());
+    assert!(!search(&di_container, "matrix")?.contains(&4));
+    Ok(())
+}
+
+#[tokio::test]
+async fn should_deletes_ids_and_skips_blocked_ones() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = container(&fixture).await?;
+    let documents = di_container.get(DOCUMENT_SERVICE_IMPL_DEP)?;
+    assert_eq!(vec![1, 2], search(&di_container, "matrix")?);
+
+    documents.delete(Entity::Movie, Language::En, &[1])?;
+    assert_eq!(vec![2], search(&di_container, "matrix")?);
+
+    di_container.get(BLOCKLIST_SERVICE_IMPL_DEP)?.block(Entity::Movie, &[5], None).await?;
+    let blocked = documents.upsert(Entity::Movie, Language::En, vec![
+        DocDetails::new(5, "Matrix Resurrections".to_string()),
+        DocDetails::new(6, "The Animatrix".to_string()),
+    ])?;
+
+    assert_eq!(vec![5], blocked);
+    assert!(search(&di_container, "resurrections")?.is_empty());
+    assert_eq!(vec![6], search(&di_container, "animatrix")?);
+    Ok(())
+}
+
+
+#[tokio::test]
+async fn should_refuses_unauthenticated_writes_while_auth_is_disabled() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.indexed_container(r#"
+[server]
+host = "127.0.0.1"
+port = 0
+
+[auth]
+enabled = false
+
+[sources.movie]
+kind = "memory"
+documents = [{ id = 1, language = "EN", title = "The Matrix" }]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, &[Entity::Movie]).await?;
+    let (base_url, shutdown) = serve(&di_container).await?;
+    let client = reqwest::Client::new();
+
+    let upsert = client.put(format!("{base_url}/documents/MOVIE/EN"))
+        .json(&vec![DocumentRequest::new(2, "The Matrix Reloaded".to_string())])
+        .send().await?;
+    let delete = client.delete(format!("{base_url}/documents/MOVIE/EN/1")).send().await?;
+    shutdown.shutdown();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, upsert.status());
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, delete.status());
+    assert_eq!(vec![1], search(&di_container, "matrix")?);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use chrono::{DateTime, TimeZone, Utc};
+use rstest::rstest;
+
+use lib::config::indexer_runner_config::{BlackoutConfig, ScheduleConfig};
+use lib::services::index_schedule::IndexSchedule;
+
+fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 8, 20, hour, minute, 0).unwrap()
+}
+
+#[rstest]
+#[case(at(10, 15), at(11, 0))]
+#[case(at(23, 30), Utc.with_ymd_and_hms(2024, 8, 21, 0, 0, 0).unwrap())]
+fn should_returns_next_cron_run(#[case] from: DateTime<Utc>, #[case] expected: DateTime<Utc>) -> anyhow::Result<()> {
+    let schedule = IndexSchedule::new(&ScheduleConfig::new(Some("0 0 * * * *".to_string()), None, 0, None))?;
+    assert_eq!(Some(expected), schedule.next_run(from));
+    Ok(())
+}
+
+#[rstest]
+#[case(at(6, 0), at(7, 0))]
+#[case(at(8, 30), at(18, 0))]
+fn should_skips_blackout_window_on_interval(#[case] from: DateTime<Utc>, #[case] expected: DateTime<Utc>) -> anyhow::Result<()> {
+    let schedule = IndexSchedule::new(&ScheduleConfig::new(None, Some(3600), 0, Some(BlackoutConfig::new(9, 18))))?;
+    assert_eq!(Some(expected), schedule.next_run(from));
+    Ok(())
+}
+
+#[test]
+fn should_skips_wrapping_blackout_window_on_cron() -> anyhow::Result<()> {
+    let schedule = IndexSchedule::new(&ScheduleConfig::new(Some("0 0 * * * *".to_string()), None, 0, Some(BlackoutConfig::new(22, 6))))?;
+    assert_eq!(Some(Utc.with_ymd_and_hms(2024, 8, 21, 6, 0, 0).unwrap()), schedule.next_run(at(21, 30)));
+    Ok(())
+}
+
+#[test]
+fn should_keeps_jittered_runs_out_of_blackout_window() -> anyhow::Result<()> {
+    let schedule = IndexSchedule::new(&ScheduleConfig::new(None, Some(3600), 7200, Some(BlackoutConfig::new(9, 18))))?;
+    for _ in 0..50 {
+        let next_run = schedule.next_run(at(6, 0)).unwrap();
+        assert!(at(7, 0) <= next_run && next_run < at(9, 0), "{next_run} is out of the jitter range");
+        assert!(!schedule.in_blackout(&next_run));
+    }
+    Ok(())
+}
+
+#[rstest]
+#[case(ScheduleConfig::new(None, None, 0, None))]
+#[case(ScheduleConfig::new(Some("invalid".to_string()), None, 0, None))]
+#[case(ScheduleConfig::new(None, Some(60), 0, Some(BlackoutConfig::new(9, 24))))]
+fn should_returns_error_on_invalid_schedule(#[case] config: ScheduleConfig) {
+    assert!(IndexSchedule::new(&config).is_err());
+}
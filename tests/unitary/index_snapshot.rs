@@ -0,0 +1,148 @@
+use std::fs;
+
+use lib::models::doc_details::DocDetails;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::services::index_processor::{IndexProcessor, IndexSearcher, IndexWriter};
+
+use crate::Fixture;
+
+fn built_processor() -> anyhow::Result<IndexProcessor> {
+    let processor = IndexProcessor::new()?;
+    processor.swap_index(Language::En, &[
+        DocDetails::new(1, "The Matrix".to_string()),
+        DocDetails::new(2, "The Matrix Reloaded".to_string()),
+        DocDetails::new(3, "Inception".to_string()),
+    ])?;
+    Ok(processor)
+}
+
+#[test]
+fn should_restores_exported_snapshot() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let dir = fixture.path("snapshots");
+    let processor = built_processor()?;
+    processor.export_snapshot(Entity::Movie, Language::En, &dir)?;
+
+    let restored = IndexProcessor::new()?;
+    restored.import_snapshot(Entity::Movie, Language::En, &dir)?;
+
+    let mut expected = processor.search(Language::En, &["matrix"])?;
+    let mut actual = restored.search(Language::En, &["matrix"])?;
+    expected.sort();
+    actual.sort();
+    assert_eq!(vec![1, 2], actual);
+    assert_eq!(expected, actual);
+    assert!(restored.last_build(Language::En).is_some());
+    Ok(())
+}
+
+#[test]
+fn should_publishes_snapshot_as_a_single_archive_holding_its_checksums() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let dir = fixture.path("snapshots");
+    let processor = built_processor()?;
+    processor.export_snapshot(Entity::Movie, Language::En, &dir)?;
+    // Re-exporting replaces the archive in place, with no checksum file to fall out of sync
+    processor.export_snapshot(Entity::Movie, Language::En, &dir)?;
+
+    let files = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(vec!["movie_en.snapshot.tar.gz"], files);
+    Ok(())
+}
+
+#[test]
+fn should_rejects_snapshot_larger_than_the_maximum() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let dir = fixture.path("snapshots");
+    let path = built_processor()?.export_snapshot(Entity::Movie, Language::En, &dir)?;
+    let archive_size = fs::metadata(path)?.len();
+
+    let restored = IndexProcessor::new()?.with_max_snapshot_bytes(archive_size - 1);
+    assert!(restored.import_snapshot(Entity::Movie, Language::En, &dir).is_err());
+    // The index files unpack to more than the compressed archive
+    let restored = IndexProcessor::new()?.with_max_snapshot_bytes(archive_size);
+    assert!(restored.import_snapshot(Entity::Movie, Language::En, &dir).is_err());
+    assert!(restored.last_build(Language::En).is_none());
+
+    let restored = IndexProcessor::new()?.with_max_snapshot_bytes(1024 * 1024);
+    restored.import_snapshot(Entity::Movie, Language::En, &dir)?;
+    let mut found = restored.search(Language::En, &["matrix"])?;
+    found.sort();
+    assert_eq!(vec![1, 2], found);
+    Ok(())
+}
+
+#[test]
+fn should_rejects_tampered_or_mismatched_snapshot() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let dir = fixture.path("snapshots");
+    let path = built_processor()?.export_snapshot(Entity::Movie, Language::En, &dir)?;
+
+    let restored = IndexProcessor::new()?;
+    assert!(restored.import_snapshot(Entity::Tv, Language::En, &dir).is_err());
+
+    let mut bytes = fs::read(&path)?;
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xFF;
+    fs::write(&path, bytes)?;
+    assert!(restored.import_snapshot(Entity::Movie, Language::En, &dir).is_err());
+    assert!(restored.last_build(Language::En).is_none());
+    Ok(())
+}
+
+#[test]
+fn should_restores_persisted_index_as_stale_until_rebuilt() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let dir = fixture.path("snapshots");
+    let processor = IndexProcessor::with_persistence(Entity::Movie, dir.clone())?;
+    processor.swap_index(Language::En, &[DocDetails::new(1, "The Matrix".to_string())])?;
+    assert!(!processor.is_stale(Language::En));
+
+    let restarted = IndexProcessor::with_persistence(Entity::Movie, dir)?;
+    assert_eq!(vec![Language::En], restarted.restore());
+    assert!(restarted.is_stale(Language::En));
+    assert_eq!(vec![1], restarted.search(Language::En, &["matrix"])?);
+
+    restarted.swap_index(Language::En, &[DocDetails::new(2, "Inception".to_string())])?;
+    assert!(!restarted.is_stale(Language::En));
+    Ok(())
+}
+
+#[test]
+fn should_persists_deleted_ids() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let dir = fixture.path("snapshots");
+    let processor = IndexProcessor::with_persistence(Entity::Movie, dir.clone())?;
+    processor.swap_index(Language::En, &[
+        DocDetails::new(1, "The Matrix".to_string()),
+        DocDetails::new(2, "The Matrix Reloaded".to_string()),
+    ])?;
+    processor.delete_ids(&[1])?;
+
+    let restarted = IndexProcessor::with_persistence(Entity::Movie, dir)?;
+    assert_eq!(vec![Language::En], restarted.restore());
+    assert_eq!(vec![2], restarted.search(Language::En, &["matrix"])?);
+    Ok(())
+}
+
+#[test]
+fn should_changes_generation_on_every_index_change() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let dir = fixture.path("snapshots");
+    let processor = built_processor()?;
+    let built = processor.generation(Language::En);
+    assert!(built > 0);
+    assert_eq!(0, processor.generation(Language::Es));
+
+    processor.swap_index(Language::En, &[DocDetails::new(4, "Memento".to_string())])?;
+    let swapped = processor.generation(Language::En);
+    assert!(swapped > built);
+
+    processor.export_snapshot(Entity::Movie, Language::En, &dir)?;
+    processor.import_snapshot(Entity::Movie, Language::En, &dir)?;
+    assert!(processor.generation(Language::En) > swapped);
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use lib::config::index_writer_config::{IndexWriterConfig, MergePolicyConfig};
+use lib::models::doc_details::DocDetails;
+use lib::models::language::Language;
+use lib::services::index_processor::{IndexProcessor, IndexSearcher, IndexWriter};
+
+fn write_twice(writer_config: IndexWriterConfig) -> anyhow::Result<IndexProcessor> {
+    let processor = IndexProcessor::new()?.with_writer_config(writer_config);
+    processor.write_all(Language::En, &[DocDetails::new(1, "The Matrix".to_string())])?;
+    processor.write_all(Language::En, &[DocDetails::new(2, "The Matrix Reloaded".to_string())])?;
+    Ok(processor)
+}
+
+#[test]
+fn should_merges_segments_after_every_build() -> anyhow::Result<()> {
+    let processor = write_twice(IndexWriterConfig::new(30, Some(1), MergePolicyConfig::None, true))?;
+
+    assert_eq!(1, processor.segment_count(Language::En));
+    assert_eq!(2, processor.search(Language::En, &["matrix"])?.len());
+    Ok(())
+}
+
+#[test]
+fn should_keeps_segments_without_forced_merge() -> anyhow::Result<()> {
+    let processor = write_twice(IndexWriterConfig::new(30, Some(1), MergePolicyConfig::None, false))?;
+
+    assert_eq!(2, processor.segment_count(Language::En));
+    processor.upsert(Language::En, &[DocDetails::new(3, "Matrix".to_string())])?;
+    assert_eq!(3, processor.segment_count(Language::En));
+    Ok(())
+}
+
+#[test]
+fn should_merges_live_write_segments_whatever_the_build_settings() -> anyhow::Result<()> {
+    let processor = write_twice(IndexWriterConfig::new(30, Some(1), MergePolicyConfig::None, false))?;
+    for id in 3..=30 {
+        processor.upsert(Language::En, &[DocDetails::new(id, format!("Matrix {id}"))])?;
+    }
+
+    // Without merges every write would leave a segment of its own
+    assert!(processor.segment_count(Language::En) < 10);
+    assert_eq!(30, processor.search(Language::En, &["matrix"])?.len());
+    Ok(())
+}
+
+#[test]
+fn should_rejects_invalid_writer_settings() {
+    let mut errors = Vec::new();
+    IndexWriterConfig::new(100, Some(1), MergePolicyConfig::default(), true).validate("movie", &mut errors);
+    IndexWriterConfig::new(100, None, MergePolicyConfig::None, false).validate("movie", &mut errors);
+    assert!(errors.is_empty());
+
+    IndexWriterConfig::new(20, Some(2), MergePolicyConfig::default(), true).validate("movie", &mut errors);
+    IndexWriterConfig::new(100, Some(0), MergePolicyConfig::default(), true).validate("tv", &mut errors);
+    IndexWriterConfig::new(100, None, MergePolicyConfig::Log { min_num_segments: Some(1), max_docs_before_merge: Some(0) }, true).validate("game", &mut errors);
+
+    assert_eq!(4, errors.len());
+    assert!(errors[0].contains("movie.memory_budget"));
+    assert!(errors[1].contains("tv.threads"));
+    assert!(errors[2].contains("min_num_segments"));
+    assert!(errors[3].contains("max_docs_before_merge"));
+}
+
+#[test]
+fn should_replays_live_writes_applied_during_a_build() -> anyhow::Result<()> {
+    let processor = IndexProcessor::new()?;
+    processor.swap_index(Language::En, &[DocDetails::new(1, "The Matrix".to_string())])?;
+
+    // Rows read before these writes still hold the old content
+    processor.begin_build(Language::En);
+    processor.upsert(Language::En, &[DocDetails::new(3, "The Matrix Revolutions".to_string())])?;
+    processor.delete(Language::En, &[1])?;
+    processor.swap_index(Language::En, &[DocDetails::new(1, "The Matrix".to_string()), DocDetails::new(2, "The Matrix Reloaded".to_string())])?;
+
+    let mut found = processor.search(Language::En, &["matrix"])?;
+    found.sort();
+    assert_eq!(vec![2, 3], found);
+
+    // Abandoned builds stop journaling, the next swap only holds its own data
+    processor.begin_build(Language::En);
+    processor.cancel_build(Language::En);
+    processor.upsert(Language::En, &[DocDetails::new(4, "Matrix".to_string())])?;
+    processor.swap_index(Language::En, &[DocDetails::new(1, "The Matrix".to_string())])?;
+    assert_eq!(vec![1], processor.search(Language::En, &["matrix"])?);
+    Ok(())
+}
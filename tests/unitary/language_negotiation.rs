@@ -0,0 +1,49 @@
+use axum::http::{HeaderMap, HeaderValue};
+use rstest::rstest;
+
+use lib::handlers::language_negotiation::{accepted_language, request_language};
+use lib::models::language::Language;
+use lib::models::search_error::SearchError;
+
+#[rstest]
+#[case("es-MX", Some(Language::Es))]
+#[case("en-GB;q=0.8, es;q=0.9", Some(Language::Es))]
+#[case("fr-FR, en-GB;q=0.8, es;q=0.8", Some(Language::En))]
+#[case("ES", Some(Language::Es))]
+#[case("fr, *;q=0.1", Some(Language::En))]
+#[case("es;q=0, fr", None)]
+#[case("es;q=abc, en;q=0.2", Some(Language::En))]
+#[case("", None)]
+fn should_negotiates_accept_language(#[case] header: &str, #[case] expected: Option<Language>) {
+    assert_eq!(expected, accepted_language(header));
+}
+
+#[rstest]
+#[case(Some("es-mx"), &[("Language", "EN")], Language::Es)]
+#[case(None, &[("Language", "es"), ("Accept-Language", "en")], Language::Es)]
+#[case(None, &[("Accept-Language", "fr, es-AR;q=0.5")], Language::Es)]
+#[case(None, &[("Accept-Language", "fr")], Language::En)]
+#[case(None, &[], Language::En)]
+fn should_resolves_request_language(#[case] explicit: Option<&str>, #[case] headers: &[(&'static str, &str)], #[case] expected: Language) -> anyhow::Result<()> {
+    let headers = to_header_map(headers)?;
+    assert_eq!(expected, request_language(explicit, &headers)?);
+    Ok(())
+}
+
+#[test]
+fn should_rejects_unsupported_or_non_ascii_explicit_language() -> anyhow::Result<()> {
+    assert!(matches!(request_language(Some("FR"), &HeaderMap::new()), Err(SearchError::InvalidLanguage(_))));
+
+    let mut headers = HeaderMap::new();
+    headers.insert("Language", HeaderValue::from_bytes("español".as_bytes())?);
+    assert!(matches!(request_language(None, &headers), Err(SearchError::InvalidLanguage(_))));
+    Ok(())
+}
+
+fn to_header_map(headers: &[(&'static str, &str)]) -> anyhow::Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.insert(*name, HeaderValue::from_str(value)?);
+    }
+    Ok(map)
+}
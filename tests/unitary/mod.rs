@@ -0,0 +1,95 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tempfile::TempDir;
+
+use lib::config::{Config, ConfigError};
+use lib::infrastructure::app_runner::AppRunner;
+use lib::infrastructure::di_container::{DIContainer, SEARCH_SERVICE_IMPL_DEP};
+use lib::infrastructure::http_server::HttpServer;
+use lib::infrastructure::shutdown::ShutdownHandle;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::services::indexer_runner::IndexerRunner;
+use lib::services::search_service_impl::SearchService;
+
+mod index_schedule;
+mod config;
+mod index_snapshot;
+mod di_container;
+mod data_sources;
+mod search_cache;
+mod language_negotiation;
+mod api_keys;
+mod analytics;
+mod click_feedback;
+mod curation;
+mod blocklist;
+mod documents;
+mod change_feed;
+mod deduplication;
+mod index_writer;
+
+/// Temporary directory holding the configuration and state files of a test, removed once dropped.
+pub struct Fixture {
+    dir: TempDir,
+}
+
+impl Fixture {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { dir: tempfile::Builder::new().prefix("content-search-").tempdir()? })
+    }
+
+    /// Path of the `name` file or directory of the fixture.
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.path().join(name)
+    }
+
+    pub fn write(&self, name: &str, content: &str) -> anyhow::Result<PathBuf> {
+        let path = self.path(name);
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    /// Loads `content` as the configuration file, replacing the one of a previous call. The process
+    /// environment is ignored, so no test depends on the variables set where it runs.
+    pub fn config(&self, content: &str) -> Result<Config, ConfigError> {
+        self.config_with_env(content, &[])
+    }
+
+    pub fn config_with_env(&self, content: &str, vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let path = self.write("config.toml", content).expect("writable fixture dir");
+        let vars = vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        Config::load_with_env(path.to_str(), vars)
+    }
+
+    /// Container of the `content` configuration, its database pool never connecting.
+    pub fn container(&self, content: &str) -> anyhow::Result<Arc<DIContainer>> {
+        let db_pool = PgPoolOptions::new().connect_lazy_with(PgConnectOptions::new());
+        AppRunner::build_container(DIContainer::new(), self.config(content)?, db_pool)
+    }
+
+    /// Same as [`Fixture::container`] with the indexes of `entities` built.
+    pub async fn indexed_container(&self, content: &str, entities: &[Entity]) -> anyhow::Result<Arc<DIContainer>> {
+        let di_container = self.container(content)?;
+        IndexerRunner::new(ShutdownHandle::new()).run_once(&di_container, entities).await?;
+        Ok(di_container)
+    }
+}
+
+pub fn search(di_container: &DIContainer, keywords: &str, language: Language, entity: Entity) -> anyhow::Result<Vec<u64>> {
+    Ok(di_container.get(SEARCH_SERVICE_IMPL_DEP)?.search(&mut keywords.to_string(), language, entity)?)
+}
+
+/// Starts the http server of `di_container`, configured with `server.port = 0`, answering its base url
+/// and the handle stopping it.
+pub async fn serve(di_container: &DIContainer) -> anyhow::Result<(String, ShutdownHandle)> {
+    let mut http_server = HttpServer::build(di_container).await?;
+    let base_url = format!("http://{}", http_server.local_addr()?);
+    let shutdown = ShutdownHandle::new();
+    let handle = shutdown.clone();
+    tokio::spawn(async move { http_server.start(handle).await });
+    Ok((base_url, shutdown))
+}
\ No newline at end of file
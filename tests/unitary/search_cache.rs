@@ -0,0 +1,44 @@
+use lib::infrastructure::di_container::{MOVIE_INDEX_PROCESSOR_DEP, SEARCH_SERVICE_IMPL_DEP};
+use lib::models::doc_details::DocDetails;
+use lib::models::entity::Entity;
+use lib::models::language::Language;
+use lib::services::index_processor::IndexWriter;
+use lib::services::search_service_impl::SearchService;
+
+use crate::Fixture;
+
+#[tokio::test]
+async fn should_invalidates_cached_searches_on_index_swap() -> anyhow::Result<()> {
+    let fixture = Fixture::new()?;
+    let di_container = fixture.indexed_container(r#"
+[search_cache]
+enabled = true
+max_entries = 100
+
+[sources.movie]
+kind = "memory"
+documents = [{ id = 1, language = "EN", title = "The Matrix" }, { id = 2, language = "ES", title = "Matrix Recargado" }]
+
+[sources.tv]
+kind = "memory"
+
+[sources.recipe]
+kind = "memory"
+
+[sources.game]
+kind = "memory"
+"#, &Entity::all()).await?;
+
+    let search_service = di_container.get(SEARCH_SERVICE_IMPL_DEP)?;
+    assert_eq!(vec![1], search_service.search(&mut "Matrix".to_string(), Language::En, Entity::Movie)?);
+    assert_eq!(vec![1], search_service.search(&mut "matrix".to_string(), Language::En, Entity::Movie)?);
+    assert_eq!(vec![2], search_service.search(&mut "matrix".to_string(), Language::Es, Entity::Movie)?);
+    assert_eq!(2, search_service.cache_entries());
+
+    di_container.get(MOVIE_INDEX_PROCESSOR_DEP)?
+        .swap_index(Language::En, &[DocDetails::new(3, "The Matrix Resurrections".to_string())])?;
+    assert_eq!(1, search_service.cache_entries());
+    assert_eq!(vec![3], search_service.search(&mut "matrix".to_string(), Language::En, Entity::Movie)?);
+    assert_eq!(vec![2], search_service.search(&mut "matrix".to_string(), Language::Es, Entity::Movie)?);
+    Ok(())
+}